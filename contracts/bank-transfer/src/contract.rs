@@ -1,9 +1,11 @@
 use crate::{
     msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    state::{Config, CONFIG},
     ContractError,
 };
 use cosmwasm_std::{
-    coin, entry_point, BankMsg, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+    entry_point, from_json, to_json_binary, BankMsg, Binary, Deps, DepsMut, Env, MessageInfo,
+    Reply, Response, StdResult,
 };
 use cw2::set_contract_version;
 
@@ -11,14 +13,26 @@ use cw2::set_contract_version;
 const CONTRACT_NAME: &str = "crates.io:bank-transfer";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+pub const SAFE_TRANSFER_REPLY_ID: u64 = 1;
+
+const TOKENFACTORY_PREFIX: &str = "tf/";
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
-    _msg: InstantiateMsg,
+    msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    let fee = execute::validate_fee(deps.as_ref(), msg.fee)?;
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: info.sender.clone(),
+            fee,
+        },
+    )?;
 
     Ok(Response::new()
         .add_attribute("method", "instantiate")
@@ -27,31 +41,664 @@ pub fn instantiate(
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
-    _deps: DepsMut,
-    _env: Env,
+    deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::BankTransfer { recipient } => {
-            // Logic for bank transfer goes here
-            let msg = BankMsg::Send {
-                to_address: recipient.clone(),
-                amount: info.funds,
-            };
-
-            // Increment logic here
-            Ok(Response::new()
-                .add_message(msg)
-                .add_attribute("method", "bank_transfer")
-                .add_attribute("recipient", recipient))
+            execute::bank_transfer(deps, env, info, recipient)
+        }
+        ExecuteMsg::SafeTransfer { recipient } => {
+            execute::safe_transfer(deps, env, info, recipient)
         }
+        ExecuteMsg::TransferFunToken { to_eth_addr } => {
+            execute::transfer_fun_token(deps, env, info, to_eth_addr)
+        }
+        ExecuteMsg::ClaimRefund {} => execute::claim_refund(deps, info),
+        ExecuteMsg::UpdateFee { fee } => execute::update_fee(deps, info, fee),
+        ExecuteMsg::Deposit {} => execute::deposit(deps, info),
+        ExecuteMsg::Withdraw { amount } => execute::withdraw(deps, info, amount),
+        ExecuteMsg::Approve {
+            spender,
+            amount,
+            expires,
+        } => execute::approve(deps, env, info, spender, amount, expires),
+        ExecuteMsg::TransferFrom {
+            owner,
+            recipient,
+            amount,
+        } => execute::transfer_from(deps, env, info, owner, recipient, amount),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(_deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        SAFE_TRANSFER_REPLY_ID => execute::refund_failed_transfer(deps, msg),
+        id => Err(ContractError::UnknownReplyId { id }),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        // Handle query messages here
+        QueryMsg::Config {} => to_json_binary(&query::query_config(deps)?),
+        QueryMsg::Refund { address } => to_json_binary(&query::query_refund(deps, address)?),
+        QueryMsg::ContractBalance { denom } => {
+            to_json_binary(&query::query_contract_balance(deps, env, denom)?)
+        }
+        QueryMsg::AllBalances {} => to_json_binary(&query::query_all_balances(deps, env)?),
+        QueryMsg::TransferHistory {
+            address,
+            start_after,
+            limit,
+        } => to_json_binary(&query::query_transfer_history(
+            deps,
+            address,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::Escrow { address } => to_json_binary(&query::query_escrow(deps, address)?),
+        QueryMsg::Allowance { owner, spender } => {
+            to_json_binary(&query::query_allowance(deps, owner, spender)?)
+        }
+    }
+}
+
+pub mod execute {
+    use cosmwasm_std::{
+        Addr, AnyMsg, Api, Coin, Coins, CosmosMsg, Empty, Event, StdError, Storage, SubMsg,
+    };
+    use cw_utils::Expiration;
+    use nibiru_std::proto::NibiruProstMsg;
+    use prost::Name;
+
+    use crate::evm::{is_eth_address, MsgConvertCoinToEvm};
+    use crate::msg::FeeConfig;
+    use crate::state::{
+        Allowance, Fee, TransferRecord, ADDRESS_TRANSFERS, ALLOWANCES, BPS_DENOMINATOR, ESCROW,
+        REFUNDS, TRANSFERS, TRANSFER_COUNT,
+    };
+
+    use super::*;
+
+    pub fn validate_fee(deps: Deps, fee: Option<FeeConfig>) -> Result<Option<Fee>, ContractError> {
+        let Some(fee) = fee else {
+            return Ok(None);
+        };
+
+        if fee.bps > BPS_DENOMINATOR {
+            return Err(ContractError::InvalidFee {
+                msg: format!("bps must be at most {BPS_DENOMINATOR}"),
+            });
+        }
+        for limit in &fee.limits {
+            if limit.min > limit.max {
+                return Err(ContractError::InvalidFee {
+                    msg: format!("min is above max for {}", limit.denom),
+                });
+            }
+        }
+
+        Ok(Some(Fee {
+            treasury: deps.api.addr_validate(&fee.treasury)?,
+            bps: fee.bps,
+            limits: fee.limits,
+        }))
+    }
+
+    /// Splits `funds` into the coins to forward and a message paying the
+    /// protocol fee to the treasury, if any fee is owed.
+    pub fn deduct_fees(
+        deps: Deps,
+        funds: Vec<Coin>,
+    ) -> Result<(Vec<Coin>, Option<BankMsg>), ContractError> {
+        let Some(fee) = CONFIG.load(deps.storage)?.fee else {
+            return Ok((funds, None));
+        };
+
+        let mut forwarded = vec![];
+        let mut fees = vec![];
+        for coin in funds {
+            let fee_amount = fee.amount_for(&coin);
+            if !fee_amount.is_zero() {
+                fees.push(Coin::new(fee_amount, &coin.denom));
+            }
+            if coin.amount > fee_amount {
+                forwarded.push(Coin::new(coin.amount - fee_amount, coin.denom));
+            }
+        }
+        if forwarded.is_empty() && !fees.is_empty() {
+            return Err(ContractError::NothingToTransfer {});
+        }
+
+        let fee_msg = (!fees.is_empty()).then(|| BankMsg::Send {
+            to_address: fee.treasury.to_string(),
+            amount: fees,
+        });
+        Ok((forwarded, fee_msg))
+    }
+
+    /// The form an address is kept in the transfer history. Bech32 addresses
+    /// go through the chain, which also accepts them in upper case, and
+    /// Ethereum addresses are lowercased, so every spelling finds the same
+    /// transfers.
+    pub fn normalize_address(api: &dyn Api, address: &str) -> StdResult<String> {
+        if is_eth_address(address) {
+            return Ok(address.to_ascii_lowercase());
+        }
+        Ok(api.addr_humanize(&api.addr_canonicalize(address)?)?.into())
+    }
+
+    pub fn record_transfer(
+        storage: &mut dyn Storage,
+        sender: &Addr,
+        recipient: &str,
+        amount: &[Coin],
+        height: u64,
+    ) -> StdResult<u64> {
+        let id = TRANSFER_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+        TRANSFER_COUNT.save(storage, &id)?;
+        TRANSFERS.save(
+            storage,
+            id,
+            &TransferRecord {
+                id,
+                sender: sender.clone(),
+                recipient: recipient.to_string(),
+                amount: amount.to_vec(),
+                height,
+            },
+        )?;
+        ADDRESS_TRANSFERS.save(storage, (sender.as_str(), id), &Empty {})?;
+        ADDRESS_TRANSFERS.save(storage, (recipient, id), &Empty {})?;
+
+        Ok(id)
+    }
+
+    fn remove_transfer(storage: &mut dyn Storage, record: &TransferRecord) {
+        TRANSFERS.remove(storage, record.id);
+        ADDRESS_TRANSFERS.remove(storage, (record.sender.as_str(), record.id));
+        ADDRESS_TRANSFERS.remove(storage, (record.recipient.as_str(), record.id));
+    }
+
+    /// Builds a `tokenfactory_denom` event with the admin and bank metadata of
+    /// every tokenfactory (`tf/...`) denom in `funds`.
+    pub fn tokenfactory_events(deps: Deps, funds: &[Coin]) -> StdResult<Vec<Event>> {
+        funds
+            .iter()
+            .filter(|coin| coin.denom.starts_with(TOKENFACTORY_PREFIX))
+            .map(|coin| {
+                let info = query::query_denom_info(deps, coin.denom.clone())?;
+                let metadata = info.metadata.unwrap_or_default();
+
+                Ok(Event::new("tokenfactory_denom")
+                    .add_attribute("denom", &coin.denom)
+                    .add_attribute("admin", info.admin)
+                    .add_attribute("name", metadata.name)
+                    .add_attribute("symbol", metadata.symbol)
+                    .add_attribute("display", metadata.display))
+            })
+            .collect()
+    }
+
+    pub fn bank_transfer(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipient: String,
+    ) -> Result<Response, ContractError> {
+        let recipient = deps.api.addr_validate(&recipient)?;
+        let (amount, fee_msg) = deduct_fees(deps.as_ref(), info.funds.clone())?;
+        record_transfer(
+            deps.storage,
+            &info.sender,
+            recipient.as_str(),
+            &amount,
+            env.block.height,
+        )?;
+        let msg = BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount,
+        };
+
+        Ok(Response::new()
+            .add_message(msg)
+            .add_messages(fee_msg)
+            .add_events(tokenfactory_events(deps.as_ref(), &info.funds)?)
+            .add_attribute("method", "bank_transfer")
+            .add_attribute("recipient", recipient))
+    }
+
+    pub fn safe_transfer(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipient: String,
+    ) -> Result<Response, ContractError> {
+        if info.funds.is_empty() {
+            return Err(ContractError::NoFunds {});
+        }
+        let recipient = deps.api.addr_validate(&recipient)?;
+
+        let (amount, fee_msg) = deduct_fees(deps.as_ref(), info.funds.clone())?;
+        let id = record_transfer(
+            deps.storage,
+            &info.sender,
+            recipient.as_str(),
+            &amount,
+            env.block.height,
+        )?;
+        let msg = BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount,
+        };
+
+        // the fee is kept even if the transfer itself fails
+        Ok(Response::new()
+            .add_messages(fee_msg)
+            // the reply finds the transfer to refund through its id
+            .add_submessage(
+                SubMsg::reply_on_error(msg, SAFE_TRANSFER_REPLY_ID)
+                    .with_payload(to_json_binary(&id)?),
+            )
+            .add_events(tokenfactory_events(deps.as_ref(), &info.funds)?)
+            .add_attribute("method", "safe_transfer")
+            .add_attribute("sender", info.sender)
+            .add_attribute("recipient", recipient))
+    }
+
+    /// Converts every attached coin into its ERC-20 FunToken representation,
+    /// credited to `to_eth_addr` by the Nibiru EVM module.
+    pub fn transfer_fun_token(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        to_eth_addr: String,
+    ) -> Result<Response, ContractError> {
+        if !is_eth_address(&to_eth_addr) {
+            return Err(ContractError::InvalidEthAddress {
+                address: to_eth_addr,
+            });
+        }
+        if info.funds.is_empty() {
+            return Err(ContractError::NoFunds {});
+        }
+
+        let (amount, fee_msg) = deduct_fees(deps.as_ref(), info.funds.clone())?;
+        record_transfer(
+            deps.storage,
+            &info.sender,
+            &to_eth_addr.to_ascii_lowercase(),
+            &amount,
+            env.block.height,
+        )?;
+
+        // the coins were sent along with this execution, so the contract is
+        // the one converting them
+        let msgs = amount.into_iter().map(|coin| {
+            let msg = MsgConvertCoinToEvm {
+                to_eth_addr: to_eth_addr.clone(),
+                sender: env.contract.address.to_string(),
+                bank_coin: Some(coin.into()),
+            };
+            CosmosMsg::Any(AnyMsg {
+                type_url: MsgConvertCoinToEvm::type_url(),
+                value: msg.to_binary(),
+            })
+        });
+
+        Ok(Response::new()
+            .add_messages(msgs)
+            .add_messages(fee_msg)
+            .add_events(tokenfactory_events(deps.as_ref(), &info.funds)?)
+            .add_attribute("method", "transfer_fun_token")
+            .add_attribute("sender", info.sender)
+            .add_attribute("to_eth_addr", to_eth_addr))
+    }
+
+    pub fn refund_failed_transfer(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+        // registered with reply_on_error, so the result is always an error
+        let error = msg.result.into_result().err().unwrap_or_default();
+        let id: u64 = from_json(&msg.payload)?;
+        let transfer = TRANSFERS.load(deps.storage, id)?;
+        // the send never happened, so it does not belong in the history
+        remove_transfer(deps.storage, &transfer);
+
+        let mut refund = Coins::try_from(
+            REFUNDS
+                .may_load(deps.storage, &transfer.sender)?
+                .unwrap_or_default(),
+        )
+        .map_err(StdError::from)?;
+        for coin in transfer.amount {
+            refund.add(coin)?;
+        }
+        REFUNDS.save(deps.storage, &transfer.sender, &refund.into_vec())?;
+
+        Ok(Response::new()
+            .add_attribute("method", "refund_failed_transfer")
+            .add_attribute("id", id.to_string())
+            .add_attribute("sender", transfer.sender)
+            .add_attribute("recipient", transfer.recipient)
+            .add_attribute("error", error))
+    }
+
+    pub fn update_fee(
+        deps: DepsMut,
+        info: MessageInfo,
+        fee: Option<FeeConfig>,
+    ) -> Result<Response, ContractError> {
+        let mut config = CONFIG.load(deps.storage)?;
+        if info.sender != config.owner {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        config.fee = validate_fee(deps.as_ref(), fee)?;
+        CONFIG.save(deps.storage, &config)?;
+
+        Ok(Response::new().add_attribute("method", "update_fee"))
+    }
+
+    pub fn deposit(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+        if info.funds.is_empty() {
+            return Err(ContractError::NoFunds {});
+        }
+
+        for coin in &info.funds {
+            ESCROW.update(
+                deps.storage,
+                (&info.sender, &coin.denom),
+                |balance| -> StdResult<_> {
+                    Ok(balance.unwrap_or_default().checked_add(coin.amount)?)
+                },
+            )?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("method", "deposit")
+            .add_attribute("owner", info.sender))
+    }
+
+    fn take_from_escrow(
+        storage: &mut dyn Storage,
+        owner: &Addr,
+        amount: &Coin,
+    ) -> Result<(), ContractError> {
+        let key = (owner, amount.denom.as_str());
+        let balance = ESCROW.may_load(storage, key)?.unwrap_or_default();
+        if balance < amount.amount {
+            return Err(ContractError::InsufficientEscrow {
+                available: Coin::new(balance, &amount.denom),
+                requested: amount.clone(),
+            });
+        }
+
+        let remaining = balance - amount.amount;
+        if remaining.is_zero() {
+            ESCROW.remove(storage, key);
+        } else {
+            ESCROW.save(storage, key, &remaining)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn withdraw(
+        deps: DepsMut,
+        info: MessageInfo,
+        amount: Coin,
+    ) -> Result<Response, ContractError> {
+        if amount.amount.is_zero() {
+            return Err(ContractError::ZeroAmount {});
+        }
+        take_from_escrow(deps.storage, &info.sender, &amount)?;
+
+        let msg = BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![amount],
+        };
+
+        Ok(Response::new()
+            .add_message(msg)
+            .add_attribute("method", "withdraw")
+            .add_attribute("owner", info.sender))
+    }
+
+    pub fn approve(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        spender: String,
+        amount: Coin,
+        expires: Option<Expiration>,
+    ) -> Result<Response, ContractError> {
+        let spender = deps.api.addr_validate(&spender)?;
+        if spender == info.sender {
+            return Err(ContractError::CannotApproveSelf {});
+        }
+
+        let expires = expires.unwrap_or_default();
+        if expires.is_expired(&env.block) {
+            return Err(ContractError::Expired {});
+        }
+
+        let key = (&info.sender, &spender, amount.denom.as_str());
+        if amount.amount.is_zero() {
+            ALLOWANCES.remove(deps.storage, key);
+        } else {
+            ALLOWANCES.save(
+                deps.storage,
+                key,
+                &Allowance {
+                    amount: amount.amount,
+                    expires,
+                },
+            )?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("method", "approve")
+            .add_attribute("owner", info.sender)
+            .add_attribute("spender", spender)
+            .add_attribute("amount", amount.to_string()))
+    }
+
+    pub fn transfer_from(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        owner: String,
+        recipient: String,
+        amount: Coin,
+    ) -> Result<Response, ContractError> {
+        let owner = deps.api.addr_validate(&owner)?;
+        let recipient = deps.api.addr_validate(&recipient)?;
+        if amount.amount.is_zero() {
+            return Err(ContractError::ZeroAmount {});
+        }
+
+        let key = (&owner, &info.sender, amount.denom.as_str());
+        let Some(mut allowance) = ALLOWANCES.may_load(deps.storage, key)? else {
+            return Err(ContractError::InsufficientAllowance {
+                available: Coin::new(0u128, &amount.denom),
+                requested: amount,
+            });
+        };
+        if allowance.expires.is_expired(&env.block) {
+            return Err(ContractError::Expired {});
+        }
+        if allowance.amount < amount.amount {
+            return Err(ContractError::InsufficientAllowance {
+                available: Coin::new(allowance.amount, &amount.denom),
+                requested: amount,
+            });
+        }
+
+        allowance.amount -= amount.amount;
+        if allowance.amount.is_zero() {
+            ALLOWANCES.remove(deps.storage, key);
+        } else {
+            ALLOWANCES.save(deps.storage, key, &allowance)?;
+        }
+        take_from_escrow(deps.storage, &owner, &amount)?;
+
+        let (forwarded, fee_msg) = deduct_fees(deps.as_ref(), vec![amount.clone()])?;
+        record_transfer(
+            deps.storage,
+            &owner,
+            recipient.as_str(),
+            &forwarded,
+            env.block.height,
+        )?;
+        let msg = BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: forwarded,
+        };
+
+        Ok(Response::new()
+            .add_message(msg)
+            .add_messages(fee_msg)
+            .add_events(tokenfactory_events(deps.as_ref(), &[amount])?)
+            .add_attribute("method", "transfer_from")
+            .add_attribute("owner", owner)
+            .add_attribute("spender", info.sender)
+            .add_attribute("recipient", recipient))
+    }
+
+    pub fn claim_refund(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+        let amount = REFUNDS
+            .may_load(deps.storage, &info.sender)?
+            .ok_or(ContractError::NoRefund {})?;
+        REFUNDS.remove(deps.storage, &info.sender);
+
+        let msg = BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount,
+        };
+
+        Ok(Response::new()
+            .add_message(msg)
+            .add_attribute("method", "claim_refund")
+            .add_attribute("recipient", info.sender))
+    }
+}
+
+pub mod query {
+    use cosmwasm_std::{Coin, Order, StdError};
+    use cw_storage_plus::Bound;
+    use nibiru_std::proto::{
+        nibiru::tokenfactory::{QueryDenomInfoRequest, QueryDenomInfoResponse},
+        NibiruProstMsg, NibiruStargateQuery,
+    };
+    use prost::Message;
+
+    use crate::msg::{
+        AllBalancesResponse, AllowanceInfo, AllowanceResponse, ConfigResponse,
+        ContractBalanceResponse, EscrowResponse, RefundResponse, TransferHistoryResponse,
+    };
+    use crate::state::{ADDRESS_TRANSFERS, ALLOWANCES, ESCROW, REFUNDS, TRANSFERS};
+
+    use super::*;
+
+    const DEFAULT_LIMIT: u32 = 10;
+    const MAX_LIMIT: u32 = 30;
+
+    pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+        let config = CONFIG.load(deps.storage)?;
+
+        Ok(ConfigResponse {
+            owner: config.owner,
+            fee: config.fee,
+        })
+    }
+
+    pub fn query_refund(deps: Deps, address: String) -> StdResult<RefundResponse> {
+        let address = deps.api.addr_validate(&address)?;
+        let amount = REFUNDS
+            .may_load(deps.storage, &address)?
+            .unwrap_or_default();
+
+        Ok(RefundResponse { amount })
+    }
+
+    pub fn query_contract_balance(
+        deps: Deps,
+        env: Env,
+        denom: String,
+    ) -> StdResult<ContractBalanceResponse> {
+        let amount = deps.querier.query_balance(env.contract.address, denom)?;
+
+        Ok(ContractBalanceResponse { amount })
+    }
+
+    pub fn query_all_balances(deps: Deps, env: Env) -> StdResult<AllBalancesResponse> {
+        #[allow(deprecated)]
+        let amount = deps.querier.query_all_balances(env.contract.address)?;
+
+        Ok(AllBalancesResponse { amount })
+    }
+
+    pub fn query_transfer_history(
+        deps: Deps,
+        address: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<TransferHistoryResponse> {
+        let address = execute::normalize_address(deps.api, &address)?;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        let transfers = ADDRESS_TRANSFERS
+            .prefix(address.as_str())
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|id| TRANSFERS.load(deps.storage, id?))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(TransferHistoryResponse { transfers })
+    }
+
+    pub fn query_escrow(deps: Deps, address: String) -> StdResult<EscrowResponse> {
+        let address = deps.api.addr_validate(&address)?;
+        let amount = ESCROW
+            .prefix(&address)
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(denom, amount)| Coin::new(amount, denom)))
+            .collect::<StdResult<_>>()?;
+
+        Ok(EscrowResponse { amount })
+    }
+
+    /// Allowances granted by `owner` to `spender`, including expired ones.
+    pub fn query_allowance(
+        deps: Deps,
+        owner: String,
+        spender: String,
+    ) -> StdResult<AllowanceResponse> {
+        let owner = deps.api.addr_validate(&owner)?;
+        let spender = deps.api.addr_validate(&spender)?;
+        let allowances = ALLOWANCES
+            .prefix((&owner, &spender))
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| {
+                item.map(|(denom, allowance)| AllowanceInfo {
+                    amount: Coin::new(allowance.amount, denom),
+                    expires: allowance.expires,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(AllowanceResponse { allowances })
+    }
+
+    pub fn query_denom_info(deps: Deps, denom: String) -> StdResult<QueryDenomInfoResponse> {
+        let query = QueryDenomInfoRequest { denom };
+
+        let res: Binary = deps.querier.query_grpc(query.path(), query.to_binary())?;
+        QueryDenomInfoResponse::decode(res.as_slice())
+            .map_err(|err| StdError::parse_err("QueryDenomInfoResponse", err))
     }
 }
@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Coin, StdError};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -12,4 +12,37 @@ pub enum ContractError {
     // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
     #[error("Overflow")]
     Overflow {},
+
+    #[error("No funds sent")]
+    NoFunds {},
+
+    #[error("No refund to claim")]
+    NoRefund {},
+
+    #[error("Invalid Ethereum address: {address}")]
+    InvalidEthAddress { address: String },
+
+    #[error("Invalid fee: {msg}")]
+    InvalidFee { msg: String },
+
+    #[error("Nothing left to transfer after fees")]
+    NothingToTransfer {},
+
+    #[error("Amount must be greater than zero")]
+    ZeroAmount {},
+
+    #[error("Insufficient escrow: {available} available, {requested} requested")]
+    InsufficientEscrow { available: Coin, requested: Coin },
+
+    #[error("Insufficient allowance: {available} available, {requested} requested")]
+    InsufficientAllowance { available: Coin, requested: Coin },
+
+    #[error("Allowance is expired")]
+    Expired {},
+
+    #[error("Cannot set an allowance for yourself")]
+    CannotApproveSelf {},
+
+    #[error("Unknown reply id: {id}")]
+    UnknownReplyId { id: u64 },
 }
@@ -0,0 +1,32 @@
+//! evm.rs: Protobuf types for the Nibiru EVM module (`eth.evm.v1`), which are
+//! not yet part of nibiru-std.
+
+use nibiru_std::proto::cosmos::base::v1beta1::Coin;
+use prost::Name;
+
+const PACKAGE_EVM: &str = "eth.evm.v1";
+
+/// MsgConvertCoinToEvm: converts bank coins of a FunToken mapping into their
+/// ERC-20 representation, credited to `to_eth_addr`.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgConvertCoinToEvm {
+    /// Hex encoded Ethereum address of the ERC-20 recipient
+    #[prost(string, tag = "1")]
+    pub to_eth_addr: ::prost::alloc::string::String,
+    /// Bech32 address of the account that holds the bank coins
+    #[prost(string, tag = "2")]
+    pub sender: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "3")]
+    pub bank_coin: ::core::option::Option<Coin>,
+}
+
+impl Name for MsgConvertCoinToEvm {
+    const NAME: &'static str = "MsgConvertCoinToEvm";
+    const PACKAGE: &'static str = PACKAGE_EVM;
+}
+
+/// Returns true if `addr` is a 0x-prefixed, 20 byte hex Ethereum address.
+pub fn is_eth_address(addr: &str) -> bool {
+    addr.strip_prefix("0x")
+        .is_some_and(|hex| hex.len() == 40 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
@@ -1,6 +1,8 @@
 pub mod contract;
 mod error;
+pub mod evm;
 pub mod msg;
+pub mod state;
 
 #[cfg(test)]
 pub mod testing;
@@ -1,13 +1,130 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Coin};
+use cw_utils::Expiration;
+
+use crate::state::{Fee, FeeLimit, TransferRecord};
 
 #[cw_serde]
-pub struct InstantiateMsg {}
+pub struct InstantiateMsg {
+    pub fee: Option<FeeConfig>,
+}
+
+#[cw_serde]
+pub struct FeeConfig {
+    pub treasury: String,
+    /// Share of each forwarded coin taken as fee, in basis points.
+    pub bps: u16,
+    pub limits: Vec<FeeLimit>,
+}
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    BankTransfer { recipient: String },
+    BankTransfer {
+        recipient: String,
+    },
+    /// Like `BankTransfer`, but a failed send credits the attached funds back
+    /// to the sender as a claimable refund instead of reverting the tx.
+    SafeTransfer {
+        recipient: String,
+    },
+    ClaimRefund {},
+    /// Converts the attached coins to their ERC-20 FunToken counterparts and
+    /// sends them to the 0x-prefixed `to_eth_addr`.
+    TransferFunToken {
+        to_eth_addr: String,
+    },
+    /// Replaces the protocol fee, `None` disables it. Only the owner may call this.
+    UpdateFee {
+        fee: Option<FeeConfig>,
+    },
+    /// Escrows the attached funds for the sender, to be spent through allowances.
+    Deposit {},
+    Withdraw {
+        amount: Coin,
+    },
+    /// Sets the amount of the sender's escrow that `spender` may pull in
+    /// `amount.denom`, replacing any previous allowance. A zero amount revokes it.
+    Approve {
+        spender: String,
+        amount: Coin,
+        expires: Option<Expiration>,
+    },
+    /// Sends `amount` out of `owner`'s escrow, spending the sender's allowance.
+    TransferFrom {
+        owner: String,
+        recipient: String,
+        amount: Coin,
+    },
 }
 
 #[cw_serde]
 #[derive(QueryResponses)]
-pub enum QueryMsg {}
+pub enum QueryMsg {
+    #[returns(ConfigResponse)]
+    Config {},
+
+    #[returns(RefundResponse)]
+    Refund { address: String },
+
+    #[returns(ContractBalanceResponse)]
+    ContractBalance { denom: String },
+
+    #[returns(AllBalancesResponse)]
+    AllBalances {},
+
+    /// Transfers sent or received by `address`, oldest first.
+    #[returns(TransferHistoryResponse)]
+    TransferHistory {
+        address: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    #[returns(EscrowResponse)]
+    Escrow { address: String },
+
+    #[returns(AllowanceResponse)]
+    Allowance { owner: String, spender: String },
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub fee: Option<Fee>,
+}
+
+#[cw_serde]
+pub struct RefundResponse {
+    pub amount: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct ContractBalanceResponse {
+    pub amount: Coin,
+}
+
+#[cw_serde]
+pub struct AllBalancesResponse {
+    pub amount: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct TransferHistoryResponse {
+    pub transfers: Vec<TransferRecord>,
+}
+
+#[cw_serde]
+pub struct EscrowResponse {
+    pub amount: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct AllowanceInfo {
+    pub amount: Coin,
+    pub expires: Expiration,
+}
+
+#[cw_serde]
+pub struct AllowanceResponse {
+    pub allowances: Vec<AllowanceInfo>,
+}
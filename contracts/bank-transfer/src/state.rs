@@ -0,0 +1,75 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Coin, Empty, Uint128};
+use cw_storage_plus::{Item, Map};
+use cw_utils::Expiration;
+
+pub const BPS_DENOMINATOR: u16 = 10_000;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+    pub fee: Option<Fee>,
+}
+
+/// Protocol fee taken from every forwarded coin and sent to `treasury`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Fee {
+    pub treasury: Addr,
+    pub bps: u16,
+    pub limits: Vec<FeeLimit>,
+}
+
+/// Bounds on the fee for a single denom, in its base units.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct FeeLimit {
+    pub denom: String,
+    pub min: Uint128,
+    pub max: Uint128,
+}
+
+impl Fee {
+    /// Fee owed on `coin`: `bps` of the amount rounded down, clamped to the
+    /// limits of its denom and never more than the amount itself.
+    pub fn amount_for(&self, coin: &Coin) -> Uint128 {
+        let mut fee = coin.amount.multiply_ratio(self.bps, BPS_DENOMINATOR);
+        if let Some(limit) = self.limits.iter().find(|limit| limit.denom == coin.denom) {
+            fee = fee.clamp(limit.min, limit.max);
+        }
+        fee.min(coin.amount)
+    }
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+// map from sender to the coins they can claim back after a failed safe transfer
+pub const REFUNDS: Map<&Addr, Vec<Coin>> = Map::new("refunds");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct TransferRecord {
+    pub id: u64,
+    pub sender: Addr,
+    pub recipient: String,
+    pub amount: Vec<Coin>,
+    pub height: u64,
+}
+
+pub const TRANSFER_COUNT: Item<u64> = Item::new("transfer_count");
+pub const TRANSFERS: Map<u64, TransferRecord> = Map::new("transfers");
+
+// index of transfer ids by participating address, both as sender and recipient
+pub const ADDRESS_TRANSFERS: Map<(&str, u64), Empty> = Map::new("address_transfers");
+
+// map from (depositor, denom) to the amount escrowed in the contract
+pub const ESCROW: Map<(&Addr, &str), Uint128> = Map::new("escrow");
+
+/// Amount of a depositor's escrow a spender may still pull.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Allowance {
+    pub amount: Uint128,
+    pub expires: Expiration,
+}
+
+// map from (owner, spender, denom) to the allowance granted by owner
+pub const ALLOWANCES: Map<(&Addr, &Addr, &str), Allowance> = Map::new("allowances");
@@ -1 +1,747 @@
+use std::marker::PhantomData;
 
+use cosmwasm_std::testing::{
+    message_info, mock_dependencies, mock_dependencies_with_balance, mock_env, MockApi,
+    MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    coin, coins, from_json, to_json_binary, Addr, AnyMsg, BankMsg, Binary, Coin, ContractResult,
+    CosmosMsg, DepsMut, Empty, OwnedDeps, Querier, QuerierResult, QueryRequest, Reply, ReplyOn,
+    SubMsgResult, SystemResult,
+};
+use cw_utils::Expiration;
+use nibiru_std::proto::cosmos::bank::v1beta1::Metadata;
+use nibiru_std::proto::nibiru::tokenfactory::QueryDenomInfoResponse;
+use prost::Message;
+
+use crate::contract::{execute, instantiate, query, reply, SAFE_TRANSFER_REPLY_ID};
+use crate::evm::MsgConvertCoinToEvm;
+use crate::msg::{
+    AllBalancesResponse, AllowanceInfo, AllowanceResponse, ConfigResponse, ContractBalanceResponse,
+    EscrowResponse, ExecuteMsg, FeeConfig, InstantiateMsg, QueryMsg, RefundResponse,
+    TransferHistoryResponse,
+};
+use crate::state::{Fee, FeeLimit};
+use crate::ContractError;
+
+const ETH_ADDR: &str = "0x000000000000000000000000000000000000bEEF";
+
+/// Answers tokenfactory denom info gRPC queries, which `MockQuerier` does not
+/// support, and forwards everything else.
+struct TokenFactoryQuerier {
+    base: MockQuerier,
+}
+
+impl Querier for TokenFactoryQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = from_json(bin_request).unwrap();
+        match request {
+            QueryRequest::Grpc(grpc) if grpc.path == "/nibiru.tokenfactory.v1.Query/DenomInfo" => {
+                let resp = QueryDenomInfoResponse {
+                    admin: "nibi1admin".to_string(),
+                    metadata: Some(Metadata {
+                        name: "Test Token".to_string(),
+                        symbol: "TEST".to_string(),
+                        display: "test".to_string(),
+                        ..Default::default()
+                    }),
+                };
+                SystemResult::Ok(ContractResult::Ok(Binary::from(resp.encode_to_vec())))
+            }
+            _ => self.base.raw_query(bin_request),
+        }
+    }
+}
+
+fn mock_dependencies_with_tokenfactory() -> OwnedDeps<MockStorage, MockApi, TokenFactoryQuerier> {
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: TokenFactoryQuerier {
+            base: MockQuerier::default(),
+        },
+        custom_query_type: PhantomData,
+    }
+}
+
+fn setup(deps: DepsMut, fee: Option<FeeConfig>) -> Addr {
+    let owner = MockApi::default().addr_make("owner");
+    instantiate(
+        deps,
+        mock_env(),
+        message_info(&owner, &[]),
+        InstantiateMsg { fee },
+    )
+    .unwrap();
+    owner
+}
+
+fn failed_reply(transfer_id: u64) -> Reply {
+    Reply {
+        id: SAFE_TRANSFER_REPLY_ID,
+        payload: to_json_binary(&transfer_id).unwrap(),
+        gas_used: 0,
+        result: SubMsgResult::Err("insufficient funds".to_string()),
+    }
+}
+
+#[test]
+fn test_safe_transfer_dispatches_submessage() {
+    let mut deps = mock_dependencies();
+    let sender = deps.api.addr_make("sender");
+    let recipient = deps.api.addr_make("recipient");
+    let info = message_info(&sender, &coins(100, "unibi"));
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        InstantiateMsg { fee: None },
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::SafeTransfer {
+            recipient: recipient.to_string(),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].id, SAFE_TRANSFER_REPLY_ID);
+    assert_eq!(res.messages[0].reply_on, ReplyOn::Error);
+    assert_eq!(res.messages[0].payload, to_json_binary(&1u64).unwrap());
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins(100, "unibi"),
+        })
+    );
+}
+
+#[test]
+fn test_safe_transfer_requires_funds() {
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut(), None);
+    let sender = deps.api.addr_make("sender");
+    let recipient = deps.api.addr_make("recipient");
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&sender, &[]),
+        ExecuteMsg::SafeTransfer {
+            recipient: recipient.to_string(),
+        },
+    )
+    .unwrap_err();
+
+    assert!(matches!(err, ContractError::NoFunds {}));
+}
+
+#[test]
+fn test_failed_transfer_is_refundable() {
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut(), None);
+    let sender = deps.api.addr_make("sender");
+    let recipient = deps.api.addr_make("recipient");
+
+    for funds in [
+        vec![coin(100, "unibi")],
+        vec![coin(50, "unibi"), coin(7, "uusd")],
+    ] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&sender, &funds),
+            ExecuteMsg::SafeTransfer {
+                recipient: recipient.to_string(),
+            },
+        )
+        .unwrap();
+    }
+    // each reply refunds its own transfer, and both accumulate into a
+    // single refund
+    for id in [2, 1] {
+        reply(deps.as_mut(), mock_env(), failed_reply(id)).unwrap();
+    }
+
+    let res: RefundResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Refund {
+                address: sender.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.amount, vec![coin(150, "unibi"), coin(7, "uusd")]);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&sender, &[]),
+        ExecuteMsg::ClaimRefund {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: sender.to_string(),
+            amount: vec![coin(150, "unibi"), coin(7, "uusd")],
+        })
+    );
+
+    // the refund can only be claimed once
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&sender, &[]),
+        ExecuteMsg::ClaimRefund {},
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::NoRefund {}));
+}
+
+#[test]
+fn test_contract_balances() {
+    let deps = mock_dependencies_with_balance(&[coin(10, "unibi"), coin(20, "uusd")]);
+
+    let res: ContractBalanceResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ContractBalance {
+                denom: "uusd".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.amount, coin(20, "uusd"));
+
+    let res: AllBalancesResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::AllBalances {}).unwrap()).unwrap();
+    assert_eq!(res.amount, vec![coin(10, "unibi"), coin(20, "uusd")]);
+}
+
+#[test]
+fn test_transfer_history() {
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut(), None);
+    let alice = deps.api.addr_make("alice");
+    let bob = deps.api.addr_make("bob");
+    let carol = deps.api.addr_make("carol");
+
+    for (sender, recipient) in [(&alice, &bob), (&bob, &carol), (&alice, &carol)] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(sender, &coins(100, "unibi")),
+            ExecuteMsg::BankTransfer {
+                recipient: recipient.to_string(),
+            },
+        )
+        .unwrap();
+    }
+
+    // a failed safe transfer is dropped from the history
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&alice, &coins(5, "unibi")),
+        ExecuteMsg::SafeTransfer {
+            recipient: bob.to_string(),
+        },
+    )
+    .unwrap();
+    reply(deps.as_mut(), mock_env(), failed_reply(4)).unwrap();
+
+    let history = |address: &str, start_after: Option<u64>, limit: Option<u32>| {
+        let res: TransferHistoryResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::TransferHistory {
+                    address: address.to_string(),
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        res.transfers
+    };
+
+    let transfers = history(alice.as_str(), None, None);
+    assert_eq!(transfers.iter().map(|t| t.id).collect::<Vec<_>>(), [1, 3]);
+    assert_eq!(transfers[0].sender, alice);
+    assert_eq!(transfers[0].recipient, bob.to_string());
+    assert_eq!(transfers[0].amount, coins(100, "unibi"));
+    assert_eq!(transfers[0].height, mock_env().block.height);
+
+    let transfers = history(carol.as_str(), None, Some(1));
+    assert_eq!(transfers.iter().map(|t| t.id).collect::<Vec<_>>(), [2]);
+    let transfers = history(carol.as_str(), Some(2), None);
+    assert_eq!(transfers.iter().map(|t| t.id).collect::<Vec<_>>(), [3]);
+}
+
+#[test]
+fn test_transfer_history_normalizes_addresses() {
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut(), None);
+    let alice = deps.api.addr_make("alice");
+    let bob = deps.api.addr_make("bob");
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&alice, &coins(100, "unibi")),
+        ExecuteMsg::BankTransfer {
+            recipient: bob.to_string(),
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&alice, &coins(100, "unibi")),
+        ExecuteMsg::TransferFunToken {
+            to_eth_addr: ETH_ADDR.to_string(),
+        },
+    )
+    .unwrap();
+
+    let history = |address: String| {
+        let res: TransferHistoryResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::TransferHistory {
+                    address,
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        res.transfers.iter().map(|t| t.id).collect::<Vec<_>>()
+    };
+    assert_eq!(history(alice.to_string()), [1, 2]);
+    assert_eq!(history(alice.to_string().to_uppercase()), [1, 2]);
+    assert_eq!(history(bob.to_string().to_uppercase()), [1]);
+    // the EVM recipient is kept lowercased and found in any case
+    assert_eq!(history(ETH_ADDR.to_string()), [2]);
+    assert_eq!(history(ETH_ADDR.to_lowercase()), [2]);
+
+    let err = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::TransferHistory {
+            address: "not an address".to_string(),
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("Error decoding bech32"));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&alice, &coins(100, "unibi")),
+        ExecuteMsg::BankTransfer {
+            recipient: "not an address".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Std(_)));
+}
+
+#[test]
+fn test_transfer_fun_token() {
+    let mut deps = mock_dependencies_with_tokenfactory();
+    setup(deps.as_mut(), None);
+    let sender = deps.api.addr_make("sender");
+    let tf_denom = format!("tf/{}/test", deps.api.addr_make("creator"));
+    let funds = vec![coin(100, "unibi"), coin(5, &tf_denom)];
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&sender, &funds),
+        ExecuteMsg::TransferFunToken {
+            to_eth_addr: ETH_ADDR.to_string(),
+        },
+    )
+    .unwrap();
+
+    // one conversion per attached coin, sent by the contract
+    assert_eq!(res.messages.len(), 2);
+    for (sub_msg, coin) in res.messages.iter().zip(&funds) {
+        let CosmosMsg::Any(AnyMsg { type_url, value }) = &sub_msg.msg else {
+            panic!("expected an Any message, got {:?}", sub_msg.msg);
+        };
+        assert_eq!(type_url, "/eth.evm.v1.MsgConvertCoinToEvm");
+        let msg = MsgConvertCoinToEvm::decode(value.as_slice()).unwrap();
+        assert_eq!(msg.to_eth_addr, ETH_ADDR);
+        assert_eq!(msg.sender, mock_env().contract.address.to_string());
+        assert_eq!(msg.bank_coin, Some(coin.clone().into()));
+    }
+
+    // only the tokenfactory denom surfaces its metadata
+    assert_eq!(res.events.len(), 1);
+    let event = &res.events[0];
+    assert_eq!(event.ty, "tokenfactory_denom");
+    let attr = |key: &str| {
+        event
+            .attributes
+            .iter()
+            .find(|attr| attr.key == key)
+            .map(|attr| attr.value.as_str())
+    };
+    assert_eq!(attr("denom"), Some(tf_denom.as_str()));
+    assert_eq!(attr("admin"), Some("nibi1admin"));
+    assert_eq!(attr("symbol"), Some("TEST"));
+}
+
+#[test]
+fn test_transfer_fun_token_invalid_eth_address() {
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut(), None);
+    let sender = deps.api.addr_make("sender");
+
+    for to_eth_addr in [
+        "",
+        "0x1234",
+        &ETH_ADDR[2..],
+        "0x000000000000000000000000000000000000zzzz",
+    ] {
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&sender, &coins(100, "unibi")),
+            ExecuteMsg::TransferFunToken {
+                to_eth_addr: to_eth_addr.to_string(),
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidEthAddress { .. }));
+    }
+}
+
+fn fee_config(bps: u16, limits: Vec<FeeLimit>) -> FeeConfig {
+    FeeConfig {
+        treasury: MockApi::default().addr_make("treasury").to_string(),
+        bps,
+        limits,
+    }
+}
+
+#[test]
+fn test_fee_amount_rounding() {
+    let fee = Fee {
+        treasury: Addr::unchecked("treasury"),
+        bps: 30,
+        limits: vec![FeeLimit {
+            denom: "uusd".to_string(),
+            min: 2u128.into(),
+            max: 50u128.into(),
+        }],
+    };
+
+    // 0.3% rounds down, so dust pays nothing without a minimum
+    assert_eq!(fee.amount_for(&coin(0, "unibi")).u128(), 0);
+    assert_eq!(fee.amount_for(&coin(1, "unibi")).u128(), 0);
+    assert_eq!(fee.amount_for(&coin(333, "unibi")).u128(), 0);
+    assert_eq!(fee.amount_for(&coin(334, "unibi")).u128(), 1);
+    assert_eq!(fee.amount_for(&coin(1_000_000, "unibi")).u128(), 3_000);
+
+    // the minimum applies to dust but never exceeds the amount itself
+    assert_eq!(fee.amount_for(&coin(1, "uusd")).u128(), 1);
+    assert_eq!(fee.amount_for(&coin(10, "uusd")).u128(), 2);
+    assert_eq!(fee.amount_for(&coin(1_000, "uusd")).u128(), 3);
+    assert_eq!(fee.amount_for(&coin(1_000_000, "uusd")).u128(), 50);
+}
+
+#[test]
+fn test_bank_transfer_with_fee() {
+    let mut deps = mock_dependencies();
+    let fee = fee_config(
+        100,
+        vec![FeeLimit {
+            denom: "uusd".to_string(),
+            min: 5u128.into(),
+            max: 10u128.into(),
+        }],
+    );
+    let treasury = fee.treasury.clone();
+    setup(deps.as_mut(), Some(fee));
+    let sender = deps.api.addr_make("sender");
+    let recipient = deps.api.addr_make("recipient");
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&sender, &[coin(1_050, "unibi"), coin(20, "uusd")]),
+        ExecuteMsg::BankTransfer {
+            recipient: recipient.to_string(),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.messages
+            .iter()
+            .map(|m| m.msg.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: vec![coin(1_040, "unibi"), coin(15, "uusd")],
+            }),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: treasury,
+                amount: vec![coin(10, "unibi"), coin(5, "uusd")],
+            }),
+        ]
+    );
+
+    // dust entirely consumed by the minimum fee leaves nothing to send
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&sender, &coins(3, "uusd")),
+        ExecuteMsg::BankTransfer {
+            recipient: recipient.to_string(),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::NothingToTransfer {}));
+}
+
+#[test]
+fn test_update_fee() {
+    let mut deps = mock_dependencies();
+    let owner = setup(deps.as_mut(), None);
+    let fee = fee_config(25, vec![]);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&MockApi::default().addr_make("anyone"), &[]),
+        ExecuteMsg::UpdateFee {
+            fee: Some(fee.clone()),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&owner, &[]),
+        ExecuteMsg::UpdateFee {
+            fee: Some(FeeConfig {
+                bps: 10_001,
+                ..fee.clone()
+            }),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidFee { .. }));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&owner, &[]),
+        ExecuteMsg::UpdateFee {
+            fee: Some(fee.clone()),
+        },
+    )
+    .unwrap();
+
+    let res: ConfigResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(res.owner, owner);
+    assert_eq!(
+        res.fee,
+        Some(Fee {
+            treasury: Addr::unchecked(fee.treasury),
+            bps: 25,
+            limits: vec![],
+        })
+    );
+}
+
+#[test]
+fn test_allowance_transfers() {
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut(), None);
+    let owner = deps.api.addr_make("depositor");
+    let spender = deps.api.addr_make("spender");
+    let recipient = deps.api.addr_make("recipient");
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&owner, &[coin(100, "unibi"), coin(50, "uusd")]),
+        ExecuteMsg::Deposit {},
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&owner, &[]),
+        ExecuteMsg::Approve {
+            spender: spender.to_string(),
+            amount: coin(60, "unibi"),
+            expires: None,
+        },
+    )
+    .unwrap();
+
+    let transfer_from = |deps: DepsMut, amount: Coin| {
+        execute(
+            deps,
+            mock_env(),
+            message_info(&spender, &[]),
+            ExecuteMsg::TransferFrom {
+                owner: owner.to_string(),
+                recipient: recipient.to_string(),
+                amount,
+            },
+        )
+    };
+
+    let res = transfer_from(deps.as_mut(), coin(40, "unibi")).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins(40, "unibi"),
+        })
+    );
+
+    let err = transfer_from(deps.as_mut(), coin(0, "unibi")).unwrap_err();
+    assert!(matches!(err, ContractError::ZeroAmount {}));
+
+    // allowances are per denom and spent as they are used
+    let err = transfer_from(deps.as_mut(), coin(21, "unibi")).unwrap_err();
+    assert!(matches!(err, ContractError::InsufficientAllowance { .. }));
+    let err = transfer_from(deps.as_mut(), coin(1, "uusd")).unwrap_err();
+    assert!(matches!(err, ContractError::InsufficientAllowance { .. }));
+
+    let res: AllowanceResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Allowance {
+                owner: owner.to_string(),
+                spender: spender.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.allowances,
+        vec![AllowanceInfo {
+            amount: coin(20, "unibi"),
+            expires: Expiration::Never {},
+        }]
+    );
+
+    let res: EscrowResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Escrow {
+                address: owner.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.amount, vec![coin(60, "unibi"), coin(50, "uusd")]);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&owner, &[]),
+        ExecuteMsg::Withdraw {
+            amount: coin(0, "unibi"),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::ZeroAmount {}));
+
+    // the owner can still withdraw, which limits what the spender can pull
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&owner, &[]),
+        ExecuteMsg::Withdraw {
+            amount: coin(50, "unibi"),
+        },
+    )
+    .unwrap();
+    let err = transfer_from(deps.as_mut(), coin(20, "unibi")).unwrap_err();
+    assert!(matches!(err, ContractError::InsufficientEscrow { .. }));
+}
+
+#[test]
+fn test_allowance_expiry() {
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut(), None);
+    let owner = deps.api.addr_make("depositor");
+    let spender = deps.api.addr_make("spender");
+    let env = mock_env();
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        message_info(&owner, &coins(100, "unibi")),
+        ExecuteMsg::Deposit {},
+    )
+    .unwrap();
+
+    let approve = |deps: DepsMut, expires: Expiration| {
+        execute(
+            deps,
+            env.clone(),
+            message_info(&owner, &[]),
+            ExecuteMsg::Approve {
+                spender: spender.to_string(),
+                amount: coin(100, "unibi"),
+                expires: Some(expires),
+            },
+        )
+    };
+
+    let err = approve(deps.as_mut(), Expiration::AtHeight(env.block.height)).unwrap_err();
+    assert!(matches!(err, ContractError::Expired {}));
+    approve(deps.as_mut(), Expiration::AtHeight(env.block.height + 10)).unwrap();
+
+    let mut later = env.clone();
+    later.block.height += 10;
+    let err = execute(
+        deps.as_mut(),
+        later,
+        message_info(&spender, &[]),
+        ExecuteMsg::TransferFrom {
+            owner: owner.to_string(),
+            recipient: spender.to_string(),
+            amount: coin(1, "unibi"),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Expired {}));
+}
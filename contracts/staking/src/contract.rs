@@ -1,57 +1,1314 @@
 use cosmwasm_std::{
-    entry_point, to_json_binary, Binary, Coin, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo,
-    Response, StakingMsg, StdResult, Uint128,
+    entry_point, to_json_binary, AnyMsg, Binary, CosmosMsg, Decimal, Deps, DepsMut, Empty, Env,
+    MessageInfo, Reply, Response, StakingMsg, StdResult,
 };
 use cw2::set_contract_version;
+use nibiru_std::proto::nibiru::tokenfactory::MsgCreateDenom;
+use nibiru_std::proto::NibiruProstMsg;
+use prost::Name;
 
 use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::state::{Batch, Config, State, BATCHES, BATCH_COUNT, CONFIG, PENDING_BATCH, STATE};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:infinite-loop";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+pub const COMPOUND_REPLY_ID: u64 = 1;
+/// Caps the compound bounty so the crank can't take most of the rewards.
+pub const MAX_COMPOUND_BOUNTY: Decimal = Decimal::percent(10);
+/// Caps the stakers signaling on one proposal, as the tally queries the
+/// balance of each of them. Once full, a new signal replaces the one with the
+/// smallest balance if it holds more.
+pub const MAX_VOTE_SIGNALS: u32 = 100;
+/// The chain's limit on unbonding entries per delegator and validator.
+/// Unstaked funds are undelegated in batches spread over the unbonding period
+/// to stay below it, leaving one entry to the owner's `Undelegate`.
+pub const MAX_UNBONDING_ENTRIES: u64 = 7;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
-    _msg: InstantiateMsg,
+    msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    execute::ensure_valid_bounty(msg.compound_bounty)?;
+
+    let mut config = Config {
+        owner: info.sender.clone(),
+        validators: vec![],
+        unbonding_period: msg.unbonding_period,
+        compound_bounty: msg.compound_bounty,
+        compound_interval: msg.compound_interval,
+        lst_denom: format!("tf/{}/{}", env.contract.address, msg.lst_subdenom),
+    };
+    for validator in msg.validators {
+        execute::ensure_valid_weight(&validator)?;
+        execute::ensure_validator_exists(deps.as_ref(), &validator.address)?;
+        if config.has_validator(&validator.address) {
+            return Err(ContractError::ValidatorAlreadyConfigured {
+                validator: validator.address,
+            });
+        }
+        config.validators.push(validator);
+    }
+    CONFIG.save(deps.storage, &config)?;
+    STATE.save(deps.storage, &State::default())?;
+    BATCH_COUNT.save(deps.storage, &1)?;
+    PENDING_BATCH.save(deps.storage, &1)?;
+    BATCHES.save(deps.storage, 1, &Batch::default())?;
+
+    let create_denom = MsgCreateDenom {
+        sender: env.contract.address.to_string(),
+        subdenom: msg.lst_subdenom,
+    };
 
     Ok(Response::new()
+        .add_message(CosmosMsg::Any(AnyMsg {
+            type_url: MsgCreateDenom::type_url(),
+            value: create_denom.to_binary(),
+        }))
         .add_attribute("method", "instantiate")
-        .add_attribute("owner", info.sender))
+        .add_attribute("owner", info.sender)
+        .add_attribute("lst_denom", config.lst_denom))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
-    _deps: DepsMut,
-    _env: Env,
+    mut deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::Run {} => {
+            let config = CONFIG.load(deps.storage)?;
+            let validator = config
+                .validators
+                .first()
+                .map(|v| v.address.clone())
+                .ok_or(ContractError::NoValidators {})?;
+            let amount = execute::payment(deps.as_ref(), &info)?;
+            let stake_msgs = execute::stake(deps.branch(), &env, &info.sender, amount.amount)?;
+
             let msg: CosmosMsg<Empty> = CosmosMsg::Staking(StakingMsg::Delegate {
-                validator: "nibivaloper1zaavvzxez0elundtn32qnk9lkm8kmcszuwx9jz".to_string(), // guard creamer's valoper address
-                amount: info.funds[0].clone(),
+                validator: validator.clone(),
+                amount: amount.clone(),
             });
 
             Ok(Response::new()
+                .add_messages(stake_msgs)
                 .add_message(msg)
                 .add_attribute("method", "run")
-                .add_attribute(
-                    "validator",
-                    "nibivaloper1zaavvzxez0elundtn32qnk9lkm8kmcszuwx9jz".to_string(),
-                )
-                .add_attribute("amount", info.funds[0].amount.to_string()))
+                .add_attribute("validator", validator)
+                .add_attribute("amount", amount.amount.to_string()))
         }
+        ExecuteMsg::Delegate { validator } => execute::delegate(deps, env, info, validator),
+        ExecuteMsg::DelegateWeighted {} => execute::delegate_weighted(deps, env, info),
+        ExecuteMsg::Rebalance {} => execute::rebalance(deps, env, info),
+        ExecuteMsg::Undelegate { validator, amount } => {
+            execute::undelegate(deps, env, info, validator, amount)
+        }
+        ExecuteMsg::Redelegate { src, dst, amount } => {
+            execute::redelegate(deps, env, info, src, dst, amount)
+        }
+        ExecuteMsg::ClaimRewards {} => execute::claim_rewards(deps, env, info),
+        ExecuteMsg::AddValidator { validator, weight } => {
+            execute::add_validator(deps, info, validator, weight)
+        }
+        ExecuteMsg::RemoveValidator { validator } => {
+            execute::remove_validator(deps, info, validator)
+        }
+        ExecuteMsg::Unstake {} => execute::unstake(deps, env, info),
+        ExecuteMsg::SubmitBatch {} => execute::submit_batch(deps, env),
+        ExecuteMsg::Claim {} => execute::claim(deps, env, info),
+        ExecuteMsg::Compound {} => execute::compound(deps, env, info),
+        ExecuteMsg::UpdateCompoundConfig { bounty, interval } => {
+            execute::update_compound_config(deps, info, bounty, interval)
+        }
+        ExecuteMsg::Vote {
+            proposal_id,
+            option,
+        } => execute::vote(deps, info, proposal_id, option),
+        ExecuteMsg::VoteWeighted {
+            proposal_id,
+            options,
+        } => execute::vote_weighted(deps, info, proposal_id, options),
+        ExecuteMsg::SignalVote {
+            proposal_id,
+            option,
+        } => execute::signal_vote(deps, info, proposal_id, option),
+        ExecuteMsg::CastSignaledVote { proposal_id } => {
+            execute::cast_signaled_vote(deps, info, proposal_id)
+        }
+        ExecuteMsg::SyncDelegations {} => execute::sync_delegations(deps, env),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        COMPOUND_REPLY_ID => execute::restake_rewards(deps, env),
+        id => Err(ContractError::UnknownReplyId { id }),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(_deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {}
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&query::query_config(deps)?),
+        QueryMsg::Staker { address } => to_json_binary(&query::query_staker(deps, env, address)?),
+        QueryMsg::Delegations {} => to_json_binary(&query::query_delegations(deps, env)?),
+        QueryMsg::Delegation { validator } => {
+            to_json_binary(&query::query_delegation(deps, env, validator)?)
+        }
+        QueryMsg::Validators {} => to_json_binary(&query::query_validators(deps)?),
+        QueryMsg::PendingRewards {} => to_json_binary(&query::query_pending_rewards(deps, env)?),
+        QueryMsg::VoteTally { proposal_id } => {
+            to_json_binary(&query::query_vote_tally(deps, proposal_id)?)
+        }
+        QueryMsg::ExchangeRate {} => to_json_binary(&query::query_exchange_rate(deps, env)?),
+    }
+}
+
+pub mod execute {
+    use cosmwasm_std::{
+        coins, Addr, BankMsg, Coin, DistributionMsg, Event, GovMsg, Order, StdError, SubMsg,
+        Uint128, VoteOption, WeightedVoteOption,
+    };
+    use cw_storage_plus::Bound;
+    use cw_utils::must_pay;
+    use nibiru_std::proto::nibiru::tokenfactory::{MsgBurn, MsgMint};
+
+    use crate::state::{
+        PendingCompound, UnbondingEntry, ValidatorWeight, LAST_BATCH_SUBMIT, LAST_COMPOUND,
+        LAST_UNDELEGATE, PENDING_COMPOUND, UNBONDING, UNCLAIMED, UNSETTLED, VOTE_SIGNALS,
+        VOTE_SIGNAL_COUNT,
+    };
+    use crate::strategy::{rebalance_moves, split_by_weight, undelegations};
+
+    use super::*;
+
+    /// The coin to stake: exactly one non-zero coin in the chain's bond denom.
+    pub fn payment(deps: Deps, info: &MessageInfo) -> Result<Coin, ContractError> {
+        let denom = deps.querier.query_bonded_denom()?;
+        let amount = must_pay(info, &denom)?;
+        Ok(Coin::new(amount, denom))
+    }
+
+    pub fn ensure_valid_weight(validator: &ValidatorWeight) -> Result<(), ContractError> {
+        if validator.weight == 0 {
+            return Err(ContractError::ZeroWeight {
+                validator: validator.address.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    pub fn ensure_validator_exists(deps: Deps, validator: &str) -> Result<(), ContractError> {
+        match deps.querier.query_validator(validator)? {
+            Some(_) => Ok(()),
+            None => Err(ContractError::ValidatorNotFound {
+                validator: validator.to_string(),
+            }),
+        }
+    }
+
+    pub fn ensure_valid_bounty(bounty: Decimal) -> Result<(), ContractError> {
+        if bounty > MAX_COMPOUND_BOUNTY {
+            return Err(ContractError::BountyTooHigh {
+                max: MAX_COMPOUND_BOUNTY,
+            });
+        }
+        Ok(())
+    }
+
+    fn ensure_owner(config: &Config, info: &MessageInfo) -> Result<(), ContractError> {
+        if info.sender != config.owner {
+            return Err(ContractError::Unauthorized {});
+        }
+        Ok(())
+    }
+
+    /// Rewards in the bond denom accumulated on each of the contract's delegations.
+    pub fn unaccrued_rewards(deps: Deps, env: &Env) -> StdResult<Vec<(String, Uint128)>> {
+        let denom = deps.querier.query_bonded_denom()?;
+        let mut rewards = vec![];
+        for delegation in deps.querier.query_all_delegations(&env.contract.address)? {
+            let Some(full) = deps
+                .querier
+                .query_delegation(&env.contract.address, &delegation.validator)?
+            else {
+                continue;
+            };
+            let amount: Uint128 = full
+                .accumulated_rewards
+                .iter()
+                .filter(|c| c.denom == denom)
+                .map(|c| c.amount)
+                .sum();
+            if !amount.is_zero() {
+                rewards.push((delegation.validator, amount));
+            }
+        }
+        Ok(rewards)
+    }
+
+    /// Lowers the principal and the pending batch, which is still delegated,
+    /// by any stake lost to slashing in proportion to their size. Returns the
+    /// loss, which is zero unless the delegations fall short of both.
+    pub fn book_slashes(
+        deps: Deps,
+        env: &Env,
+        state: &mut State,
+        pending: &mut Batch,
+    ) -> StdResult<Uint128> {
+        let delegated: Uint128 = deps
+            .querier
+            .query_all_delegations(&env.contract.address)?
+            .iter()
+            .map(|d| d.amount.amount)
+            .sum();
+        let expected = state.total_staked + pending.value;
+        if delegated >= expected {
+            return Ok(Uint128::zero());
+        }
+        let loss = expected - delegated;
+        let batch_loss = loss.multiply_ratio(pending.value, expected);
+        pending.value -= batch_loss;
+        state.total_staked -= loss - batch_loss;
+        Ok(loss)
+    }
+
+    /// Withdraws the rewards of every delegation into the contract, where they
+    /// wait for the next compound. The staking module also withdraws rewards
+    /// whenever a delegation changes, so the returned messages must come before
+    /// any staking message for those rewards to be accounted for. Slashes are
+    /// booked first, so shares are never priced on stake that is gone.
+    pub fn accrue_rewards(deps: DepsMut, env: &Env) -> StdResult<(State, Vec<CosmosMsg>)> {
+        let mut state = STATE.load(deps.storage)?;
+        let pending_id = PENDING_BATCH.load(deps.storage)?;
+        let mut pending = BATCHES.load(deps.storage, pending_id)?;
+        if !book_slashes(deps.as_ref(), env, &mut state, &mut pending)?.is_zero() {
+            BATCHES.save(deps.storage, pending_id, &pending)?;
+        }
+        let rewards = unaccrued_rewards(deps.as_ref(), env)?;
+        state.idle_rewards += rewards.iter().map(|(_, amount)| *amount).sum::<Uint128>();
+        STATE.save(deps.storage, &state)?;
+
+        let msgs = rewards
+            .into_iter()
+            .map(|(validator, _)| withdraw_rewards(validator))
+            .collect();
+        Ok((state, msgs))
+    }
+
+    fn withdraw_rewards(validator: String) -> CosmosMsg {
+        CosmosMsg::Distribution(DistributionMsg::WithdrawDelegatorReward { validator })
+    }
+
+    fn lst_msg<M: NibiruProstMsg + Name>(msg: M) -> CosmosMsg {
+        CosmosMsg::Any(AnyMsg {
+            type_url: M::type_url(),
+            value: msg.to_binary(),
+        })
+    }
+
+    /// Mints liquid staking tokens worth `amount` to `recipient`, after the
+    /// messages withdrawing the rewards accrued so far.
+    pub fn stake(
+        mut deps: DepsMut,
+        env: &Env,
+        recipient: &Addr,
+        amount: Uint128,
+    ) -> StdResult<Vec<CosmosMsg>> {
+        let config = CONFIG.load(deps.storage)?;
+        let (mut state, mut msgs) = accrue_rewards(deps.branch(), env)?;
+        let shares = state.amount_to_shares(amount);
+        state.total_shares += shares;
+        state.total_staked += amount;
+        STATE.save(deps.storage, &state)?;
+
+        msgs.push(lst_msg(MsgMint {
+            sender: env.contract.address.to_string(),
+            coin: Some(Coin::new(shares, config.lst_denom).into()),
+            mint_to: recipient.to_string(),
+        }));
+        Ok(msgs)
+    }
+
+    pub fn delegate(
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        validator: String,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        if !config.has_validator(&validator) {
+            return Err(ContractError::ValidatorNotConfigured { validator });
+        }
+        // the set may outlive a validator that has since been removed from the chain
+        ensure_validator_exists(deps.as_ref(), &validator)?;
+        let amount = payment(deps.as_ref(), &info)?;
+        let stake_msgs = stake(deps.branch(), &env, &info.sender, amount.amount)?;
+
+        let msg: CosmosMsg<Empty> = CosmosMsg::Staking(StakingMsg::Delegate {
+            validator: validator.clone(),
+            amount: amount.clone(),
+        });
+
+        Ok(Response::new()
+            .add_messages(stake_msgs)
+            .add_message(msg)
+            .add_attribute("method", "delegate")
+            .add_attribute("validator", validator)
+            .add_attribute("amount", amount.amount.to_string()))
+    }
+
+    pub fn delegate_weighted(
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        if config.validators.is_empty() {
+            return Err(ContractError::NoValidators {});
+        }
+        let amount = payment(deps.as_ref(), &info)?;
+        let stake_msgs = stake(deps.branch(), &env, &info.sender, amount.amount)?;
+
+        // validators whose share rounds down to zero are skipped, as the
+        // staking module rejects empty delegations
+        let msgs = split_by_weight(amount.amount, &config.validators)
+            .into_iter()
+            .filter(|(_, share)| !share.is_zero())
+            .map(|(validator, share)| {
+                CosmosMsg::<Empty>::Staking(StakingMsg::Delegate {
+                    validator,
+                    amount: Coin::new(share, &amount.denom),
+                })
+            });
+
+        Ok(Response::new()
+            .add_messages(stake_msgs)
+            .add_messages(msgs)
+            .add_attribute("method", "delegate_weighted")
+            .add_attribute("amount", amount.to_string()))
+    }
+
+    pub fn rebalance(
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        ensure_owner(&config, &info)?;
+        if config.validators.is_empty() {
+            return Err(ContractError::NoValidators {});
+        }
+
+        let (_, withdraw_msgs) = accrue_rewards(deps.branch(), &env)?;
+        let delegations = deps.querier.query_all_delegations(&env.contract.address)?;
+        let Some(denom) = delegations.first().map(|d| d.amount.denom.clone()) else {
+            return Ok(Response::new().add_attribute("method", "rebalance"));
+        };
+        let current: Vec<(String, Uint128)> = delegations
+            .into_iter()
+            .map(|d| (d.validator, d.amount.amount))
+            .collect();
+
+        let msgs = rebalance_moves(&current, &config.validators)
+            .into_iter()
+            .map(|m| {
+                CosmosMsg::<Empty>::Staking(StakingMsg::Redelegate {
+                    src_validator: m.src,
+                    dst_validator: m.dst,
+                    amount: Coin::new(m.amount, &denom),
+                })
+            });
+
+        Ok(Response::new()
+            .add_messages(withdraw_msgs)
+            .add_messages(msgs)
+            .add_attribute("method", "rebalance"))
+    }
+
+    pub fn undelegate(
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        validator: String,
+        amount: Coin,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        ensure_owner(&config, &info)?;
+        if let Some(last) = LAST_UNDELEGATE.may_load(deps.storage)? {
+            let next = last.plus_seconds(config.unbonding_period);
+            if env.block.time < next {
+                return Err(ContractError::UnbondingTooSoon { next });
+            }
+        }
+        let denom = deps.querier.query_bonded_denom()?;
+        if amount.denom != denom {
+            return Err(ContractError::WrongDenom { expected: denom });
+        }
+        if amount.amount.is_zero() {
+            return Err(ContractError::ZeroAmount {});
+        }
+        let (mut state, withdraw_msgs) = accrue_rewards(deps.branch(), &env)?;
+        // the funds leave the pool, so every share is worth less and
+        // `SyncDelegations` doesn't mistake the gap for a slash
+        state.total_staked = state
+            .total_staked
+            .checked_sub(amount.amount)
+            .map_err(StdError::from)?;
+        STATE.save(deps.storage, &state)?;
+
+        // they come back as a batch of their own that only the owner claims
+        let batch_id = BATCH_COUNT.load(deps.storage)? + 1;
+        BATCH_COUNT.save(deps.storage, &batch_id)?;
+        let release_at = env.block.time.plus_seconds(config.unbonding_period);
+        BATCHES.save(
+            deps.storage,
+            batch_id,
+            &Batch {
+                requested: amount.amount,
+                value: amount.amount,
+                release_at: Some(release_at),
+                settled: false,
+            },
+        )?;
+        UNSETTLED.save(deps.storage, (release_at.seconds(), batch_id), &Empty {})?;
+        UNBONDING.update(deps.storage, &info.sender, |entries| -> StdResult<_> {
+            let mut entries = entries.unwrap_or_default();
+            entries.push(UnbondingEntry {
+                batch: batch_id,
+                amount: amount.amount,
+            });
+            Ok(entries)
+        })?;
+        LAST_UNDELEGATE.save(deps.storage, &env.block.time)?;
+
+        // validators removed from the set can still be undelegated from
+        let msg: CosmosMsg<Empty> = CosmosMsg::Staking(StakingMsg::Undelegate {
+            validator: validator.clone(),
+            amount: amount.clone(),
+        });
+
+        Ok(Response::new()
+            .add_messages(withdraw_msgs)
+            .add_message(msg)
+            .add_attribute("method", "undelegate")
+            .add_attribute("validator", validator)
+            .add_attribute("amount", amount.to_string())
+            .add_attribute("batch", batch_id.to_string()))
+    }
+
+    pub fn redelegate(
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        src: String,
+        dst: String,
+        amount: Coin,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        ensure_owner(&config, &info)?;
+        if !config.has_validator(&dst) {
+            return Err(ContractError::ValidatorNotConfigured { validator: dst });
+        }
+        ensure_validator_exists(deps.as_ref(), &dst)?;
+        let (_, withdraw_msgs) = accrue_rewards(deps.branch(), &env)?;
+
+        let msg: CosmosMsg<Empty> = CosmosMsg::Staking(StakingMsg::Redelegate {
+            src_validator: src.clone(),
+            dst_validator: dst.clone(),
+            amount: amount.clone(),
+        });
+
+        Ok(Response::new()
+            .add_messages(withdraw_msgs)
+            .add_message(msg)
+            .add_attribute("method", "redelegate")
+            .add_attribute("src", src)
+            .add_attribute("dst", dst)
+            .add_attribute("amount", amount.to_string()))
+    }
+
+    pub fn claim_rewards(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        ensure_owner(&config, &info)?;
+
+        let (_, msgs) = accrue_rewards(deps, &env)?;
+
+        Ok(Response::new()
+            .add_messages(msgs)
+            .add_attribute("method", "claim_rewards"))
+    }
+
+    pub fn add_validator(
+        deps: DepsMut,
+        info: MessageInfo,
+        validator: String,
+        weight: u64,
+    ) -> Result<Response, ContractError> {
+        let mut config = CONFIG.load(deps.storage)?;
+        ensure_owner(&config, &info)?;
+        if config.has_validator(&validator) {
+            return Err(ContractError::ValidatorAlreadyConfigured { validator });
+        }
+        ensure_validator_exists(deps.as_ref(), &validator)?;
+        let validator = ValidatorWeight {
+            address: validator,
+            weight,
+        };
+        ensure_valid_weight(&validator)?;
+
+        config.validators.push(validator.clone());
+        CONFIG.save(deps.storage, &config)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "add_validator")
+            .add_attribute("validator", validator.address)
+            .add_attribute("weight", validator.weight.to_string()))
+    }
+
+    pub fn remove_validator(
+        deps: DepsMut,
+        info: MessageInfo,
+        validator: String,
+    ) -> Result<Response, ContractError> {
+        let mut config = CONFIG.load(deps.storage)?;
+        ensure_owner(&config, &info)?;
+        if !config.has_validator(&validator) {
+            return Err(ContractError::ValidatorNotConfigured { validator });
+        }
+
+        config.validators.retain(|v| v.address != validator);
+        CONFIG.save(deps.storage, &config)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "remove_validator")
+            .add_attribute("validator", validator))
+    }
+
+    pub fn unstake(
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        let shares = must_pay(&info, &config.lst_denom)?;
+        let (mut state, withdraw_msgs) = accrue_rewards(deps.branch(), &env)?;
+        let amount = state.shares_to_amount(shares);
+        if amount.is_zero() {
+            return Err(ContractError::ZeroAmount {});
+        }
+        // the share of the idle rewards is liquid already and paid out right
+        // away, only the rest has to be undelegated
+        let rewards = state.shares_to_rewards(shares);
+        let staked = amount - rewards;
+        state.total_shares -= shares;
+        state.total_staked -= staked;
+        state.idle_rewards -= rewards;
+        STATE.save(deps.storage, &state)?;
+        let burn = lst_msg(MsgBurn {
+            sender: env.contract.address.to_string(),
+            coin: Some(Coin::new(shares, &config.lst_denom).into()),
+            burn_from: env.contract.address.to_string(),
+        });
+
+        // the staked part waits in the pending batch, `SubmitBatch`
+        // undelegates it together with everyone else's
+        let batch_id = PENDING_BATCH.load(deps.storage)?;
+        if !staked.is_zero() {
+            BATCHES.update(deps.storage, batch_id, |batch| -> StdResult<_> {
+                let mut batch = batch.unwrap_or_default();
+                batch.requested += staked;
+                batch.value += staked;
+                Ok(batch)
+            })?;
+            UNBONDING.update(deps.storage, &info.sender, |entries| -> StdResult<_> {
+                let mut entries = entries.unwrap_or_default();
+                match entries.iter_mut().find(|entry| entry.batch == batch_id) {
+                    Some(entry) => entry.amount += staked,
+                    None => entries.push(UnbondingEntry {
+                        batch: batch_id,
+                        amount: staked,
+                    }),
+                }
+                Ok(entries)
+            })?;
+        }
+
+        let mut res = Response::new()
+            .add_messages(withdraw_msgs)
+            .add_message(burn);
+        // the withdrawals above come first, so the rewards are in the balance
+        if !rewards.is_zero() {
+            let denom = deps.querier.query_bonded_denom()?;
+            res = res.add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: coins(rewards.u128(), &denom),
+            });
+        }
+
+        Ok(res
+            .add_attribute("method", "unstake")
+            .add_attribute("shares", shares.to_string())
+            .add_attribute("amount", staked.to_string())
+            .add_attribute("rewards", rewards.to_string())
+            .add_attribute("batch", batch_id.to_string()))
+    }
+
+    /// Seconds between two batch submissions. Submitting at most once per
+    /// interval keeps `MAX_UNBONDING_ENTRIES - 2` entries per validator
+    /// unbonding at once. One of the remaining two is the owner's, the other
+    /// covers the oldest entry, which may only complete at the end of the
+    /// block a new one is added in.
+    pub fn batch_interval(config: &Config) -> u64 {
+        config.unbonding_period.div_ceil(MAX_UNBONDING_ENTRIES - 2)
+    }
+
+    pub fn submit_batch(mut deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        if let Some(last) = LAST_BATCH_SUBMIT.may_load(deps.storage)? {
+            let next = last.plus_seconds(batch_interval(&config));
+            if env.block.time < next {
+                return Err(ContractError::UnbondingTooSoon { next });
+            }
+        }
+        let batch_id = PENDING_BATCH.load(deps.storage)?;
+        let mut batch = BATCHES.load(deps.storage, batch_id)?;
+        if batch.requested.is_zero() {
+            return Err(ContractError::EmptyBatch {});
+        }
+        let (_, withdraw_msgs) = accrue_rewards(deps.branch(), &env)?;
+
+        let denom = deps.querier.query_bonded_denom()?;
+        let current: Vec<(String, Uint128)> = deps
+            .querier
+            .query_all_delegations(&env.contract.address)?
+            .into_iter()
+            .map(|d| (d.validator, d.amount.amount))
+            .collect();
+        // delegations only fall short of the batch after a slash, in which
+        // case its stakers get back what is left
+        let picked = undelegations(&current, batch.value);
+        batch.value = picked.iter().map(|(_, amount)| *amount).sum();
+        let release_at = env.block.time.plus_seconds(config.unbonding_period);
+        batch.release_at = Some(release_at);
+        BATCHES.save(deps.storage, batch_id, &batch)?;
+        UNSETTLED.save(deps.storage, (release_at.seconds(), batch_id), &Empty {})?;
+
+        let next_id = BATCH_COUNT.load(deps.storage)? + 1;
+        BATCH_COUNT.save(deps.storage, &next_id)?;
+        BATCHES.save(deps.storage, next_id, &Batch::default())?;
+        PENDING_BATCH.save(deps.storage, &next_id)?;
+        LAST_BATCH_SUBMIT.save(deps.storage, &env.block.time)?;
+
+        let msgs = picked.into_iter().map(|(validator, amount)| {
+            CosmosMsg::<Empty>::Staking(StakingMsg::Undelegate {
+                validator,
+                amount: Coin::new(amount, &denom),
+            })
+        });
+        Ok(Response::new()
+            .add_messages(withdraw_msgs)
+            .add_messages(msgs)
+            .add_attribute("method", "submit_batch")
+            .add_attribute("batch", batch_id.to_string())
+            .add_attribute("amount", batch.value.to_string())
+            .add_attribute("release_at", release_at.seconds().to_string()))
+    }
+
+    pub fn claim(
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        settle_batches(deps.branch(), &env)?;
+
+        let mut recorded = Uint128::zero();
+        let mut unbonded = Uint128::zero();
+        let mut arriving = false;
+        let mut unbonding = vec![];
+        for entry in UNBONDING
+            .may_load(deps.storage, &info.sender)?
+            .unwrap_or_default()
+        {
+            let mut batch = BATCHES.load(deps.storage, entry.batch)?;
+            if !batch.settled {
+                arriving |= batch
+                    .release_at
+                    .is_some_and(|release_at| release_at <= env.block.time);
+                unbonding.push(entry);
+                continue;
+            }
+            // a slash during unbonding is shared by the batch pro rata, and
+            // the last staker to claim gets what rounding left over
+            let paid = entry.amount.multiply_ratio(batch.value, batch.requested);
+            batch.requested -= entry.amount;
+            batch.value -= paid;
+            if batch.requested.is_zero() {
+                BATCHES.remove(deps.storage, entry.batch);
+            } else {
+                BATCHES.save(deps.storage, entry.batch, &batch)?;
+            }
+            recorded += entry.amount;
+            unbonded += paid;
+        }
+        if recorded.is_zero() {
+            return Err(if arriving {
+                ContractError::UnbondingNotReceived {}
+            } else {
+                ContractError::NothingToClaim {}
+            });
+        }
+        if unbonding.is_empty() {
+            UNBONDING.remove(deps.storage, &info.sender);
+        } else {
+            UNBONDING.save(deps.storage, &info.sender, &unbonding)?;
+        }
+        UNCLAIMED.update(deps.storage, |unclaimed| -> StdResult<_> {
+            Ok(unclaimed - unbonded)
+        })?;
+
+        let mut res = Response::new();
+        if !unbonded.is_zero() {
+            let denom = deps.querier.query_bonded_denom()?;
+            res = res.add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: coins(unbonded.u128(), denom),
+            });
+        }
+        Ok(res
+            .add_attribute("method", "claim")
+            .add_attribute("recorded", recorded.to_string())
+            .add_attribute("unbonded", unbonded.to_string()))
+    }
+
+    /// Settles the released batches, oldest first, whose funds have arrived.
+    /// The staking module only pays out matured undelegations at the end of
+    /// the block, and an unbonding period configured too short has the same
+    /// effect, so a batch is only settled once the balance exceeds what is
+    /// already accounted for. What exceeds it is the batch's funds, less than
+    /// was undelegated if it was slashed during unbonding.
+    fn settle_batches(deps: DepsMut, env: &Env) -> StdResult<()> {
+        let released: Vec<(u64, u64)> = UNSETTLED
+            .keys(
+                deps.storage,
+                None,
+                Some(Bound::inclusive((env.block.time.seconds(), u64::MAX))),
+                Order::Ascending,
+            )
+            .collect::<StdResult<_>>()?;
+        if released.is_empty() {
+            return Ok(());
+        }
+
+        let denom = deps.querier.query_bonded_denom()?;
+        let balance = deps
+            .querier
+            .query_balance(&env.contract.address, denom)?
+            .amount;
+        let idle_rewards = STATE.load(deps.storage)?.idle_rewards;
+        let mut unclaimed = UNCLAIMED.may_load(deps.storage)?.unwrap_or_default();
+        for (release_at, id) in released {
+            let received = balance.saturating_sub(idle_rewards + unclaimed);
+            if received.is_zero() {
+                break;
+            }
+            let mut batch = BATCHES.load(deps.storage, id)?;
+            batch.value = batch.value.min(received);
+            batch.settled = true;
+            unclaimed += batch.value;
+            BATCHES.save(deps.storage, id, &batch)?;
+            UNSETTLED.remove(deps.storage, (release_at, id));
+        }
+        UNCLAIMED.save(deps.storage, &unclaimed)
+    }
+
+    pub fn compound(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        if config.validators.is_empty() {
+            return Err(ContractError::NoValidators {});
+        }
+        if let Some(last) = LAST_COMPOUND.may_load(deps.storage)? {
+            let next = last.plus_seconds(config.compound_interval);
+            if env.block.time < next {
+                return Err(ContractError::CompoundTooSoon { next });
+            }
+        }
+
+        // the withdrawn amount is only known once the withdrawals have
+        // executed, so the reply to the last one reads it from the balance
+        let denom = deps.querier.query_bonded_denom()?;
+        let balance_before = deps
+            .querier
+            .query_balance(&env.contract.address, denom)?
+            .amount;
+        PENDING_COMPOUND.save(
+            deps.storage,
+            &PendingCompound {
+                caller: info.sender,
+                balance_before,
+            },
+        )?;
+        LAST_COMPOUND.save(deps.storage, &env.block.time)?;
+
+        let rewards = unaccrued_rewards(deps.as_ref(), &env)?;
+        let Some(((last, _), rest)) = rewards.split_last() else {
+            // a stake or unstake since the last compound has withdrawn
+            // everything already, which leaves only the idle rewards
+            return restake_rewards(deps, env);
+        };
+
+        Ok(Response::new()
+            .add_messages(
+                rest.iter()
+                    .map(|(validator, _)| withdraw_rewards(validator.clone())),
+            )
+            .add_submessage(SubMsg::reply_on_success(
+                withdraw_rewards(last.clone()),
+                COMPOUND_REPLY_ID,
+            ))
+            .add_attribute("method", "compound"))
+    }
+
+    pub fn restake_rewards(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+        let pending = PENDING_COMPOUND.load(deps.storage)?;
+        PENDING_COMPOUND.remove(deps.storage);
+        let config = CONFIG.load(deps.storage)?;
+
+        let denom = deps.querier.query_bonded_denom()?;
+        let balance = deps
+            .querier
+            .query_balance(&env.contract.address, &denom)?
+            .amount;
+        // rewards withdrawn by earlier delegation changes are restaked too
+        let mut state = STATE.load(deps.storage)?;
+        let withdrawn = balance
+            .checked_sub(pending.balance_before)
+            .map_err(StdError::from)?;
+        let rewards = withdrawn + std::mem::take(&mut state.idle_rewards);
+        if rewards.is_zero() {
+            return Err(ContractError::NothingToCompound {});
+        }
+        let bounty = rewards.mul_floor(config.compound_bounty);
+        let restaked = rewards - bounty;
+
+        // restaked rewards raise the exchange rate of the liquid staking token
+        state.total_staked += restaked;
+        STATE.save(deps.storage, &state)?;
+
+        let msgs = split_by_weight(restaked, &config.validators)
+            .into_iter()
+            .filter(|(_, share)| !share.is_zero())
+            .map(|(validator, share)| {
+                CosmosMsg::<Empty>::Staking(StakingMsg::Delegate {
+                    validator,
+                    amount: Coin::new(share, &denom),
+                })
+            });
+        let mut res = Response::new().add_messages(msgs);
+        if !bounty.is_zero() {
+            res = res.add_message(BankMsg::Send {
+                to_address: pending.caller.to_string(),
+                amount: coins(bounty.u128(), &denom),
+            });
+        }
+
+        Ok(res
+            .add_attribute("method", "restake_rewards")
+            .add_attribute("rewards", rewards.to_string())
+            .add_attribute("bounty", bounty.to_string())
+            .add_attribute("restaked", restaked.to_string()))
+    }
+
+    pub fn update_compound_config(
+        deps: DepsMut,
+        info: MessageInfo,
+        bounty: Decimal,
+        interval: u64,
+    ) -> Result<Response, ContractError> {
+        let mut config = CONFIG.load(deps.storage)?;
+        ensure_owner(&config, &info)?;
+        ensure_valid_bounty(bounty)?;
+
+        config.compound_bounty = bounty;
+        config.compound_interval = interval;
+        CONFIG.save(deps.storage, &config)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "update_compound_config")
+            .add_attribute("bounty", bounty.to_string())
+            .add_attribute("interval", interval.to_string()))
+    }
+
+    pub fn ensure_valid_vote_weights(options: &[WeightedVoteOption]) -> Result<(), ContractError> {
+        let mut total = Decimal::zero();
+        for (i, o) in options.iter().enumerate() {
+            if o.weight.is_zero() || options[..i].iter().any(|p| p.option == o.option) {
+                return Err(ContractError::InvalidVoteWeights {});
+            }
+            total += o.weight;
+        }
+        if total != Decimal::one() {
+            return Err(ContractError::InvalidVoteWeights {});
+        }
+        Ok(())
+    }
+
+    pub fn vote(
+        deps: DepsMut,
+        info: MessageInfo,
+        proposal_id: u64,
+        option: VoteOption,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        ensure_owner(&config, &info)?;
+
+        Ok(Response::new()
+            .add_message(GovMsg::Vote {
+                proposal_id,
+                option,
+            })
+            .add_attribute("method", "vote")
+            .add_attribute("proposal_id", proposal_id.to_string()))
+    }
+
+    pub fn vote_weighted(
+        deps: DepsMut,
+        info: MessageInfo,
+        proposal_id: u64,
+        options: Vec<WeightedVoteOption>,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        ensure_owner(&config, &info)?;
+        ensure_valid_vote_weights(&options)?;
+
+        Ok(Response::new()
+            .add_message(GovMsg::VoteWeighted {
+                proposal_id,
+                options,
+            })
+            .add_attribute("method", "vote_weighted")
+            .add_attribute("proposal_id", proposal_id.to_string()))
+    }
+
+    pub fn signal_vote(
+        deps: DepsMut,
+        info: MessageInfo,
+        proposal_id: u64,
+        option: VoteOption,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        let shares = deps
+            .querier
+            .query_balance(&info.sender, &config.lst_denom)?
+            .amount;
+        if shares.is_zero() {
+            return Err(ContractError::NotStaker {});
+        }
+
+        let mut res = Response::new()
+            .add_attribute("method", "signal_vote")
+            .add_attribute("proposal_id", proposal_id.to_string())
+            .add_attribute("staker", info.sender.as_str());
+        // changing an earlier signal doesn't count against the cap
+        if !VOTE_SIGNALS.has(deps.storage, (proposal_id, &info.sender)) {
+            let count = VOTE_SIGNAL_COUNT
+                .may_load(deps.storage, proposal_id)?
+                .unwrap_or_default();
+            if count < MAX_VOTE_SIGNALS {
+                VOTE_SIGNAL_COUNT.save(deps.storage, proposal_id, &(count + 1))?;
+            } else {
+                // a full set of signals can't lock out larger holders, who
+                // take the place of the smallest one
+                let (smallest, smallest_shares) =
+                    smallest_signal(deps.as_ref(), &config.lst_denom, proposal_id)?;
+                if shares <= smallest_shares {
+                    return Err(ContractError::TooManyVoteSignals {
+                        proposal_id,
+                        max: MAX_VOTE_SIGNALS,
+                    });
+                }
+                VOTE_SIGNALS.remove(deps.storage, (proposal_id, &smallest));
+                res = res.add_attribute("replaced", smallest);
+            }
+        }
+        VOTE_SIGNALS.save(deps.storage, (proposal_id, &info.sender), &option)?;
+
+        Ok(res)
+    }
+
+    /// The signaler on `proposal_id` holding the fewest liquid staking tokens now.
+    fn smallest_signal(
+        deps: Deps,
+        lst_denom: &str,
+        proposal_id: u64,
+    ) -> StdResult<(Addr, Uint128)> {
+        let mut smallest: Option<(Addr, Uint128)> = None;
+        for staker in
+            VOTE_SIGNALS
+                .prefix(proposal_id)
+                .keys(deps.storage, None, None, Order::Ascending)
+        {
+            let staker = staker?;
+            let shares = deps.querier.query_balance(&staker, lst_denom)?.amount;
+            if smallest.as_ref().is_none_or(|(_, least)| shares < *least) {
+                smallest = Some((staker, shares));
+            }
+        }
+        smallest.ok_or_else(|| StdError::generic_err("no vote signals"))
+    }
+
+    /// The chain only keeps the latest vote of the contract, so this is owner
+    /// only like `Vote` and replaces any vote cast before.
+    pub fn cast_signaled_vote(
+        deps: DepsMut,
+        info: MessageInfo,
+        proposal_id: u64,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        ensure_owner(&config, &info)?;
+        let options = query::query_vote_tally(deps.as_ref(), proposal_id)?.options;
+        if options.is_empty() {
+            return Err(ContractError::NoVoteSignals { proposal_id });
+        }
+
+        Ok(Response::new()
+            .add_message(GovMsg::VoteWeighted {
+                proposal_id,
+                options,
+            })
+            .add_attribute("method", "cast_signaled_vote")
+            .add_attribute("proposal_id", proposal_id.to_string()))
+    }
+
+    pub fn sync_delegations(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+        let mut state = STATE.load(deps.storage)?;
+        let pending_id = PENDING_BATCH.load(deps.storage)?;
+        let mut pending = BATCHES.load(deps.storage, pending_id)?;
+        let recorded = state.total_staked;
+        // lowering the total lowers the value of every share, so each staker
+        // bears the loss in proportion to their stake. Slashed unbonding
+        // entries can't be queried, those are settled when claiming.
+        let loss = book_slashes(deps.as_ref(), &env, &mut state, &mut pending)?;
+
+        let mut res = Response::new()
+            .add_attribute("method", "sync_delegations")
+            .add_attribute("recorded", recorded.to_string());
+        if !loss.is_zero() {
+            STATE.save(deps.storage, &state)?;
+            BATCHES.save(deps.storage, pending_id, &pending)?;
+            res = res.add_event(
+                Event::new("slash_detected")
+                    .add_attribute("loss", loss.to_string())
+                    .add_attribute("total_staked", state.total_staked.to_string()),
+            );
+        }
+
+        Ok(res)
+    }
+}
+
+pub mod query {
+    use cosmwasm_std::{Coin, Order, Uint128, VoteOption, WeightedVoteOption};
+
+    use crate::msg::{
+        ConfigResponse, DelegationResponse, DelegationsResponse, ExchangeRateResponse,
+        PendingRewardsResponse, StakerResponse, UnbondingInfo, ValidatorInfo, ValidatorRewards,
+        ValidatorsResponse, VoteTallyResponse,
+    };
+    use crate::state::{UNBONDING, VOTE_SIGNALS};
+
+    use super::*;
+
+    pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+        let config = CONFIG.load(deps.storage)?;
+
+        Ok(ConfigResponse {
+            owner: config.owner,
+            validators: config.validators,
+            unbonding_period: config.unbonding_period,
+            compound_bounty: config.compound_bounty,
+            compound_interval: config.compound_interval,
+            lst_denom: config.lst_denom,
+        })
+    }
+
+    pub fn query_staker(deps: Deps, env: Env, address: String) -> StdResult<StakerResponse> {
+        let address = deps.api.addr_validate(&address)?;
+        let config = CONFIG.load(deps.storage)?;
+        let state = current_state(deps, &env)?;
+        let shares = deps
+            .querier
+            .query_balance(&address, config.lst_denom)?
+            .amount;
+
+        let pending_rewards = state.shares_to_rewards(shares);
+
+        Ok(StakerResponse {
+            principal: state.shares_to_amount(shares) - pending_rewards,
+            shares,
+            pending_rewards,
+            unbonding: UNBONDING
+                .may_load(deps.storage, &address)?
+                .unwrap_or_default()
+                .into_iter()
+                .map(|entry| {
+                    Ok(UnbondingInfo {
+                        batch: entry.batch,
+                        amount: entry.amount,
+                        release_at: BATCHES.load(deps.storage, entry.batch)?.release_at,
+                    })
+                })
+                .collect::<StdResult<_>>()?,
+        })
+    }
+
+    pub fn query_delegations(deps: Deps, env: Env) -> StdResult<DelegationsResponse> {
+        Ok(DelegationsResponse {
+            delegations: deps.querier.query_all_delegations(env.contract.address)?,
+        })
+    }
+
+    pub fn query_delegation(
+        deps: Deps,
+        env: Env,
+        validator: String,
+    ) -> StdResult<DelegationResponse> {
+        Ok(DelegationResponse {
+            delegation: deps
+                .querier
+                .query_delegation(env.contract.address, validator)?,
+        })
+    }
+
+    pub fn query_validators(deps: Deps) -> StdResult<ValidatorsResponse> {
+        let config = CONFIG.load(deps.storage)?;
+        let mut active = deps.querier.query_all_validators()?;
+
+        let validators = config
+            .validators
+            .into_iter()
+            .map(|v| {
+                let validator = active
+                    .iter()
+                    .position(|a| a.address == v.address)
+                    .map(|i| active.swap_remove(i));
+                ValidatorInfo {
+                    address: v.address,
+                    weight: v.weight,
+                    validator,
+                }
+            })
+            .collect();
+        Ok(ValidatorsResponse { validators })
+    }
+
+    pub fn query_pending_rewards(deps: Deps, env: Env) -> StdResult<PendingRewardsResponse> {
+        let denom = deps.querier.query_bonded_denom()?;
+        let rewards: Vec<ValidatorRewards> = execute::unaccrued_rewards(deps, &env)?
+            .into_iter()
+            .map(|(validator, amount)| ValidatorRewards {
+                validator,
+                amount: Coin::new(amount, &denom),
+            })
+            .collect();
+        let total: Uint128 = rewards.iter().map(|r| r.amount.amount).sum();
+
+        Ok(PendingRewardsResponse {
+            rewards,
+            total: Coin::new(total, denom),
+        })
+    }
+
+    pub fn query_vote_tally(deps: Deps, proposal_id: u64) -> StdResult<VoteTallyResponse> {
+        let config = CONFIG.load(deps.storage)?;
+        let mut tally: Vec<(VoteOption, Uint128)> = [
+            VoteOption::Yes,
+            VoteOption::No,
+            VoteOption::Abstain,
+            VoteOption::NoWithVeto,
+        ]
+        .into_iter()
+        .map(|option| (option, Uint128::zero()))
+        .collect();
+
+        // signals are weighted by the tokens held now, so stakers who left
+        // since signaling no longer count
+        for signal in
+            VOTE_SIGNALS
+                .prefix(proposal_id)
+                .range(deps.storage, None, None, Order::Ascending)
+        {
+            let (staker, option) = signal?;
+            let shares = deps
+                .querier
+                .query_balance(&staker, &config.lst_denom)?
+                .amount;
+            if let Some((_, total)) = tally.iter_mut().find(|(o, _)| *o == option) {
+                *total += shares;
+            }
+        }
+        tally.retain(|(_, shares)| !shares.is_zero());
+
+        let total: Uint128 = tally.iter().map(|(_, shares)| *shares).sum();
+        // the chain rejects zero weights, so options with too few shares to
+        // show up are left out
+        let mut options: Vec<WeightedVoteOption> = tally
+            .iter()
+            .map(|(option, shares)| WeightedVoteOption {
+                option: option.clone(),
+                weight: Decimal::from_ratio(*shares, total),
+            })
+            .filter(|o| !o.weight.is_zero())
+            .collect();
+        // the weights must add up to exactly one, so the rounding leftover
+        // goes to the option with the most shares
+        let largest = tally.iter().max_by_key(|(_, shares)| *shares);
+        if let Some((largest, _)) = largest {
+            let distributed: Decimal = options.iter().map(|o| o.weight).sum();
+            if let Some(o) = options.iter_mut().find(|o| o.option == *largest) {
+                o.weight += Decimal::one() - distributed;
+            }
+        }
+
+        Ok(VoteTallyResponse { tally, options })
+    }
+
+    /// The state as if slashes had been booked and the rewards on the
+    /// delegations accrued, which staking or unstaking now would do first.
+    fn current_state(deps: Deps, env: &Env) -> StdResult<State> {
+        let mut state = STATE.load(deps.storage)?;
+        let mut pending = BATCHES.load(deps.storage, PENDING_BATCH.load(deps.storage)?)?;
+        execute::book_slashes(deps, env, &mut state, &mut pending)?;
+        state.idle_rewards += execute::unaccrued_rewards(deps, env)?
+            .into_iter()
+            .map(|(_, amount)| amount)
+            .sum::<Uint128>();
+        Ok(state)
+    }
+
+    pub fn query_exchange_rate(deps: Deps, env: Env) -> StdResult<ExchangeRateResponse> {
+        let config = CONFIG.load(deps.storage)?;
+        let state = current_state(deps, &env)?;
+
+        Ok(ExchangeRateResponse {
+            lst_denom: config.lst_denom,
+            rate: state.exchange_rate(),
+            total_staked: state.total_staked,
+            total_supply: state.total_shares,
+        })
+    }
 }
@@ -1,4 +1,5 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Decimal, StdError, Timestamp};
+use cw_utils::PaymentError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -12,4 +13,81 @@ pub enum ContractError {
     // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
     #[error("Overflow")]
     Overflow {},
+
+    #[error("Validator {validator} does not exist")]
+    ValidatorNotFound { validator: String },
+
+    #[error("Validator {validator} is not in the configured set")]
+    ValidatorNotConfigured { validator: String },
+
+    #[error("Validator {validator} is already in the configured set")]
+    ValidatorAlreadyConfigured { validator: String },
+
+    #[error("No validators configured")]
+    NoValidators {},
+
+    #[error("Validator {validator} must have a non-zero weight")]
+    ZeroWeight { validator: String },
+
+    #[error("No funds sent")]
+    NoFunds {},
+
+    #[error("Only one denomination can be sent")]
+    MultipleDenoms {},
+
+    #[error("Must send the bond denom {expected}")]
+    WrongDenom { expected: String },
+
+    #[error("{0}")]
+    Payment(PaymentError),
+
+    #[error("Amount must be greater than zero")]
+    ZeroAmount {},
+
+    #[error("Nothing to claim")]
+    NothingToClaim {},
+
+    #[error("The unbonded funds haven't arrived yet")]
+    UnbondingNotReceived {},
+
+    #[error("Compound bounty can be at most {max}")]
+    BountyTooHigh { max: Decimal },
+
+    #[error("Cannot compound again before {next}")]
+    CompoundTooSoon { next: Timestamp },
+
+    #[error("Cannot undelegate again before {next}")]
+    UnbondingTooSoon { next: Timestamp },
+
+    #[error("No unstaked funds to undelegate")]
+    EmptyBatch {},
+
+    #[error("Nothing to compound")]
+    NothingToCompound {},
+
+    #[error("Unknown reply id {id}")]
+    UnknownReplyId { id: u64 },
+
+    #[error("Vote weights must be positive, unique per option and sum to one")]
+    InvalidVoteWeights {},
+
+    #[error("Only stakers can signal votes")]
+    NotStaker {},
+
+    #[error("No stake behind the signals for proposal {proposal_id}")]
+    NoVoteSignals { proposal_id: u64 },
+
+    #[error("Proposal {proposal_id} already has the maximum of {max} vote signals")]
+    TooManyVoteSignals { proposal_id: u64, max: u32 },
+}
+
+impl From<PaymentError> for ContractError {
+    fn from(err: PaymentError) -> Self {
+        match err {
+            PaymentError::NoFunds {} => ContractError::NoFunds {},
+            PaymentError::MultipleDenoms {} => ContractError::MultipleDenoms {},
+            PaymentError::MissingDenom(expected) => ContractError::WrongDenom { expected },
+            err => ContractError::Payment(err),
+        }
+    }
 }
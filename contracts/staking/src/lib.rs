@@ -1,5 +1,12 @@
 pub mod contract;
 mod error;
 pub mod msg;
+pub mod state;
+pub mod strategy;
+
+#[cfg(test)]
+mod multitest;
+#[cfg(test)]
+pub mod testing;
 
 pub use crate::error::ContractError;
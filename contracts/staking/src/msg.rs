@@ -1,13 +1,206 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{
+    Addr, Coin, Decimal, Delegation, FullDelegation, Timestamp, Uint128, Validator, VoteOption,
+    WeightedVoteOption,
+};
+
+use crate::state::ValidatorWeight;
 
 #[cw_serde]
-pub struct InstantiateMsg {}
+pub struct InstantiateMsg {
+    pub validators: Vec<ValidatorWeight>,
+    /// The chain's unbonding time in seconds.
+    pub unbonding_period: u64,
+    /// Share of compounded rewards paid to the caller, at most 10%.
+    pub compound_bounty: Decimal,
+    /// Minimum number of seconds between two compounds.
+    pub compound_interval: u64,
+    /// The liquid staking token is created as `tf/<contract>/<lst_subdenom>`.
+    pub lst_subdenom: String,
+}
 
 #[cw_serde]
 pub enum ExecuteMsg {
+    /// Delegates the attached funds to the first configured validator.
     Run {},
+    Delegate {
+        validator: String,
+    },
+    /// Splits the attached funds across the configured validators by weight.
+    DelegateWeighted {},
+    /// Redelegates the contract's stake toward the configured weights.
+    Rebalance {},
+    /// Takes `amount` out of the pool, lowering the value of every share, and
+    /// undelegates it for the owner to `Claim` once unbonded. Allowed once
+    /// per unbonding period, as batches leave a single unbonding entry free.
+    Undelegate {
+        validator: String,
+        amount: Coin,
+    },
+    /// Moves stake from `src` to `dst`, which must be a configured validator.
+    Redelegate {
+        src: String,
+        dst: String,
+        amount: Coin,
+    },
+    /// Withdraws the rewards of every delegation to the contract.
+    ClaimRewards {},
+    AddValidator {
+        validator: String,
+        weight: u64,
+    },
+    RemoveValidator {
+        validator: String,
+    },
+    /// Burns the attached liquid staking tokens and adds what they are worth
+    /// to the pending batch. The funds can be claimed once the batch has been
+    /// submitted and the unbonding period has passed.
+    Unstake {},
+    /// Undelegates the pending batch and starts a new one. Anyone can call
+    /// this once per batch interval, a fifth of the unbonding period.
+    SubmitBatch {},
+    /// Pays out the sender's unbonding entries whose funds have arrived. When
+    /// a slash during unbonding left less than was undelegated, the stakers in
+    /// the batch share the shortfall pro rata.
+    Claim {},
+    /// Withdraws the rewards of every delegation and delegates them again,
+    /// minus a bounty for the caller. Anyone can call this once the compound
+    /// interval has passed.
+    Compound {},
+    UpdateCompoundConfig {
+        bounty: Decimal,
+        interval: u64,
+    },
+    /// Votes on a governance proposal with the contract's stake.
+    Vote {
+        proposal_id: u64,
+        option: VoteOption,
+    },
+    /// Splits the contract's vote across options, weights must sum to one.
+    VoteWeighted {
+        proposal_id: u64,
+        options: Vec<WeightedVoteOption>,
+    },
+    /// Records the sender's preferred option on a proposal. Up to
+    /// `MAX_VOTE_SIGNALS` stakers can signal on each proposal, after that a
+    /// signal replaces the one backed by the fewest tokens if it has more.
+    SignalVote {
+        proposal_id: u64,
+        option: VoteOption,
+    },
+    /// Casts a weighted vote from the signals of the current stakers,
+    /// replacing any earlier vote of the contract. Owner only.
+    CastSignaledVote {
+        proposal_id: u64,
+    },
+    /// Reduces every staker's principal by any stake lost to slashing and
+    /// reports the loss. Staking and unstaking book slashes the same way
+    /// before pricing shares.
+    SyncDelegations {},
 }
 
 #[cw_serde]
 #[derive(QueryResponses)]
-pub enum QueryMsg {}
+pub enum QueryMsg {
+    #[returns(ConfigResponse)]
+    Config {},
+    #[returns(StakerResponse)]
+    Staker { address: String },
+    /// All of the contract's delegations.
+    #[returns(DelegationsResponse)]
+    Delegations {},
+    /// The contract's delegation to `validator`, including accumulated rewards.
+    #[returns(DelegationResponse)]
+    Delegation { validator: String },
+    /// The configured validators along with their on-chain details.
+    #[returns(ValidatorsResponse)]
+    Validators {},
+    /// Rewards accumulated on the contract's delegations but not yet withdrawn.
+    #[returns(PendingRewardsResponse)]
+    PendingRewards {},
+    /// The weighted vote `CastSignaledVote` would cast right now.
+    #[returns(VoteTallyResponse)]
+    VoteTally { proposal_id: u64 },
+    /// The bond denom each liquid staking token can be unstaked for.
+    #[returns(ExchangeRateResponse)]
+    ExchangeRate {},
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub validators: Vec<ValidatorWeight>,
+    pub unbonding_period: u64,
+    pub compound_bounty: Decimal,
+    pub compound_interval: u64,
+    pub lst_denom: String,
+}
+
+#[cw_serde]
+pub struct StakerResponse {
+    /// The current value of `shares`, including compounded rewards.
+    pub principal: Uint128,
+    /// The address' balance of the liquid staking token.
+    pub shares: Uint128,
+    /// Share of the rewards that the next compound adds to the principal.
+    pub pending_rewards: Uint128,
+    pub unbonding: Vec<UnbondingInfo>,
+}
+
+#[cw_serde]
+pub struct UnbondingInfo {
+    pub batch: u64,
+    pub amount: Uint128,
+    /// `None` while the batch waits to be submitted.
+    pub release_at: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct DelegationsResponse {
+    pub delegations: Vec<Delegation>,
+}
+
+#[cw_serde]
+pub struct DelegationResponse {
+    pub delegation: Option<FullDelegation>,
+}
+
+#[cw_serde]
+pub struct ValidatorInfo {
+    pub address: String,
+    pub weight: u64,
+    /// `None` when the validator is no longer in the active set.
+    pub validator: Option<Validator>,
+}
+
+#[cw_serde]
+pub struct ValidatorsResponse {
+    pub validators: Vec<ValidatorInfo>,
+}
+
+#[cw_serde]
+pub struct ValidatorRewards {
+    pub validator: String,
+    pub amount: Coin,
+}
+
+#[cw_serde]
+pub struct PendingRewardsResponse {
+    pub rewards: Vec<ValidatorRewards>,
+    pub total: Coin,
+}
+
+#[cw_serde]
+pub struct VoteTallyResponse {
+    /// Shares behind each option that was signaled.
+    pub tally: Vec<(VoteOption, Uint128)>,
+    pub options: Vec<WeightedVoteOption>,
+}
+
+#[cw_serde]
+pub struct ExchangeRateResponse {
+    pub lst_denom: String,
+    pub rate: Decimal,
+    pub total_staked: Uint128,
+    pub total_supply: Uint128,
+}
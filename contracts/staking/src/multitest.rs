@@ -0,0 +1,671 @@
+use anyhow::bail;
+use cosmwasm_std::testing::{mock_env, MockApi, MockStorage};
+use cosmwasm_std::{
+    coin, coins, Addr, AnyMsg, Api, BankMsg, BlockInfo, Coin, CustomMsg, CustomQuery, Decimal,
+    Empty, Storage, Uint128, Validator,
+};
+use cw_multi_test::error::AnyResult;
+use cw_multi_test::{
+    App, AppBuilder, AppResponse, BankKeeper, BankSudo, ContractWrapper, CosmosRouter,
+    DistributionKeeper, Executor, FailingModule, GovFailingModule, IbcFailingModule, StakeKeeper,
+    StakingInfo, StakingSudo, Stargate, SudoMsg, WasmKeeper,
+};
+use nibiru_std::proto::nibiru::tokenfactory::{MsgBurn, MsgCreateDenom, MsgMint};
+use prost::{Message, Name};
+use serde::de::DeserializeOwned;
+
+use crate::contract::{execute, instantiate, query, reply};
+use crate::msg::{ExchangeRateResponse, ExecuteMsg, InstantiateMsg, QueryMsg, StakerResponse};
+use crate::state::ValidatorWeight;
+use crate::ContractError;
+
+const DENOM: &str = "unibi";
+const UNBONDING_TIME: u64 = 60;
+
+/// Handles the tokenfactory messages of the liquid staking token with the bank
+/// module, which is all the contract relies on.
+struct TokenFactory;
+
+impl Stargate for TokenFactory {
+    fn execute_any<ExecC, QueryC>(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        _sender: Addr,
+        msg: AnyMsg,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: CustomMsg + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        let value = msg.value.as_slice();
+        if msg.type_url == MsgCreateDenom::type_url() {
+            Ok(AppResponse::default())
+        } else if msg.type_url == MsgMint::type_url() {
+            let msg = MsgMint::decode(value)?;
+            let coin = msg.coin.unwrap_or_default();
+            let sudo = BankSudo::Mint {
+                to_address: msg.mint_to,
+                amount: coins(coin.amount.parse()?, coin.denom),
+            };
+            router.sudo(api, storage, block, sudo.into())
+        } else if msg.type_url == MsgBurn::type_url() {
+            let msg = MsgBurn::decode(value)?;
+            let coin = msg.coin.unwrap_or_default();
+            let burn = BankMsg::Burn {
+                amount: coins(coin.amount.parse()?, coin.denom),
+            };
+            router.execute(
+                api,
+                storage,
+                block,
+                Addr::unchecked(msg.burn_from),
+                burn.into(),
+            )
+        } else {
+            bail!("unexpected any message: {}", msg.type_url)
+        }
+    }
+}
+
+type TokenFactoryApp = App<
+    BankKeeper,
+    MockApi,
+    MockStorage,
+    FailingModule<Empty, Empty, Empty>,
+    WasmKeeper<Empty, Empty>,
+    StakeKeeper,
+    DistributionKeeper,
+    IbcFailingModule,
+    GovFailingModule,
+    TokenFactory,
+>;
+
+struct Suite {
+    app: TokenFactoryApp,
+    owner: Addr,
+    contract: Addr,
+    validator_1: String,
+    validator_2: String,
+}
+
+impl Suite {
+    fn new() -> Self {
+        let api = MockApi::default();
+        let owner = api.addr_make("owner");
+        let validator_1 = api.addr_make("validator1").to_string();
+        let validator_2 = api.addr_make("validator2").to_string();
+
+        let mut app =
+            AppBuilder::new()
+                .with_stargate(TokenFactory)
+                .build(|router, api, storage| {
+                    router
+                        .bank
+                        .init_balance(storage, &owner, coins(1_000, DENOM))
+                        .unwrap();
+                    router
+                        .staking
+                        .setup(
+                            storage,
+                            StakingInfo {
+                                bonded_denom: DENOM.to_string(),
+                                unbonding_time: UNBONDING_TIME,
+                                apr: Decimal::percent(10),
+                            },
+                        )
+                        .unwrap();
+                    for address in [&validator_1, &validator_2] {
+                        let validator = Validator::create(
+                            address.clone(),
+                            Decimal::percent(5),
+                            Decimal::percent(20),
+                            Decimal::percent(1),
+                        );
+                        router
+                            .staking
+                            .add_validator(api, storage, &mock_env().block, validator)
+                            .unwrap();
+                    }
+                });
+
+        let code_id = app.store_code(Box::new(
+            ContractWrapper::new(execute, instantiate, query).with_reply(reply),
+        ));
+        let contract = app
+            .instantiate_contract(
+                code_id,
+                owner.clone(),
+                &InstantiateMsg {
+                    validators: [&validator_1, &validator_2]
+                        .into_iter()
+                        .map(|address| ValidatorWeight {
+                            address: address.clone(),
+                            weight: 1,
+                        })
+                        .collect(),
+                    unbonding_period: UNBONDING_TIME,
+                    // a year on 1,000 staked earns about 95, enough for a non-zero bounty
+                    compound_bounty: Decimal::percent(5),
+                    compound_interval: 60 * 60,
+                    lst_subdenom: "stnibi".to_string(),
+                },
+                &[],
+                "staking",
+                None,
+            )
+            .unwrap();
+
+        Suite {
+            app,
+            owner,
+            contract,
+            validator_1,
+            validator_2,
+        }
+    }
+
+    fn execute(&mut self, sender: &Addr, msg: ExecuteMsg, funds: &[Coin]) -> anyhow::Result<()> {
+        self.app
+            .execute_contract(sender.clone(), self.contract.clone(), &msg, funds)
+            .map(|_| ())
+    }
+
+    fn delegated(&self, validator: &str) -> u128 {
+        self.app
+            .wrap()
+            .query_delegation(&self.contract, validator)
+            .unwrap()
+            .map(|d| d.amount.amount.u128())
+            .unwrap_or_default()
+    }
+
+    fn balance(&self) -> u128 {
+        self.balance_of(&self.contract)
+    }
+
+    fn balance_of(&self, address: &Addr) -> u128 {
+        self.app
+            .wrap()
+            .query_balance(address, DENOM)
+            .unwrap()
+            .amount
+            .u128()
+    }
+
+    fn lst_denom(&self) -> String {
+        format!("tf/{}/stnibi", self.contract)
+    }
+
+    fn exchange_rate(&self) -> ExchangeRateResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.contract, &QueryMsg::ExchangeRate {})
+            .unwrap()
+    }
+
+    fn staker(&self, address: &Addr) -> StakerResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                &self.contract,
+                &QueryMsg::Staker {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap()
+    }
+}
+
+#[test]
+fn test_undelegate_and_redelegate() {
+    let mut suite = Suite::new();
+    let owner = suite.owner.clone();
+    let (validator_1, validator_2) = (suite.validator_1.clone(), suite.validator_2.clone());
+
+    suite
+        .execute(
+            &owner,
+            ExecuteMsg::Delegate {
+                validator: validator_1.clone(),
+            },
+            &coins(300, DENOM),
+        )
+        .unwrap();
+    assert_eq!(suite.delegated(&validator_1), 300);
+
+    suite
+        .execute(
+            &owner,
+            ExecuteMsg::Redelegate {
+                src: validator_1.clone(),
+                dst: validator_2.clone(),
+                amount: coin(100, DENOM),
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(suite.delegated(&validator_1), 200);
+    assert_eq!(suite.delegated(&validator_2), 100);
+    // moving stake between validators leaves its value unchanged
+    assert_eq!(suite.staker(&owner).principal.u128(), 300);
+
+    suite
+        .execute(
+            &owner,
+            ExecuteMsg::Undelegate {
+                validator: validator_1.clone(),
+                amount: coin(200, DENOM),
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(suite.delegated(&validator_1), 0);
+    assert_eq!(suite.balance(), 0);
+    // the undelegated funds no longer back the shares, which isn't a slash
+    assert_eq!(suite.staker(&owner).principal.u128(), 100);
+    let res = suite
+        .app
+        .execute_contract(
+            owner.clone(),
+            suite.contract.clone(),
+            &ExecuteMsg::SyncDelegations {},
+            &[],
+        )
+        .unwrap();
+    assert!(!res.events.iter().any(|e| e.ty == "wasm-slash_detected"));
+
+    let err = suite
+        .execute(
+            &owner,
+            ExecuteMsg::Undelegate {
+                validator: validator_2.clone(),
+                amount: coin(50, DENOM),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::UnbondingTooSoon { .. }
+    ));
+
+    // the undelegated funds come back to the owner once unbonding completes
+    suite
+        .app
+        .update_block(|block| block.time = block.time.plus_seconds(UNBONDING_TIME + 1));
+    assert_eq!(suite.balance(), 200);
+    let before = suite.balance_of(&owner);
+    suite.execute(&owner, ExecuteMsg::Claim {}, &[]).unwrap();
+    assert_eq!(suite.balance_of(&owner), before + 200);
+    assert_eq!(suite.balance(), 0);
+}
+
+#[test]
+fn test_claim_rewards() {
+    let mut suite = Suite::new();
+    let owner = suite.owner.clone();
+    let (validator_1, validator_2) = (suite.validator_1.clone(), suite.validator_2.clone());
+
+    suite
+        .execute(
+            &owner,
+            ExecuteMsg::DelegateWeighted {},
+            &coins(1_000, DENOM),
+        )
+        .unwrap();
+    suite
+        .app
+        .update_block(|block| block.time = block.time.plus_seconds(60 * 60 * 24 * 365));
+
+    let accumulated: u128 = [&validator_1, &validator_2]
+        .into_iter()
+        .map(|validator| {
+            suite
+                .app
+                .wrap()
+                .query_delegation(&suite.contract, validator)
+                .unwrap()
+                .unwrap()
+                .accumulated_rewards
+                .iter()
+                .map(|reward| reward.amount.u128())
+                .sum::<u128>()
+        })
+        .sum();
+    assert!(accumulated > 0);
+
+    suite
+        .execute(&owner, ExecuteMsg::ClaimRewards {}, &[])
+        .unwrap();
+    assert_eq!(suite.balance(), accumulated);
+}
+
+#[test]
+fn test_staking_management_is_owner_only() {
+    let mut suite = Suite::new();
+    let anyone = MockApi::default().addr_make("anyone");
+    let validator_1 = suite.validator_1.clone();
+
+    for msg in [
+        ExecuteMsg::Undelegate {
+            validator: validator_1.clone(),
+            amount: coin(1, DENOM),
+        },
+        ExecuteMsg::Redelegate {
+            src: validator_1.clone(),
+            dst: suite.validator_2.clone(),
+            amount: coin(1, DENOM),
+        },
+        ExecuteMsg::ClaimRewards {},
+    ] {
+        let err = suite.execute(&anyone, msg, &[]).unwrap_err();
+        assert_eq!(
+            err.root_cause().to_string(),
+            ContractError::Unauthorized {}.to_string()
+        );
+    }
+}
+
+#[test]
+fn test_unstake_and_claim() {
+    let mut suite = Suite::new();
+    let owner = suite.owner.clone();
+    let lst_denom = suite.lst_denom();
+
+    suite
+        .execute(
+            &owner,
+            ExecuteMsg::DelegateWeighted {},
+            &coins(1_000, DENOM),
+        )
+        .unwrap();
+    assert_eq!(
+        suite.app.wrap().query_balance(&owner, &lst_denom).unwrap(),
+        coin(1_000, &lst_denom)
+    );
+    suite
+        .app
+        .update_block(|block| block.time = block.time.plus_seconds(60 * 60 * 24 * 365));
+
+    suite
+        .execute(&owner, ExecuteMsg::Unstake {}, &coins(400, &lst_denom))
+        .unwrap();
+    let staker = suite.staker(&owner);
+    assert_eq!(staker.shares, Uint128::new(600));
+    assert_eq!(staker.principal, Uint128::new(600));
+    assert_eq!(staker.unbonding.len(), 1);
+    assert_eq!(staker.unbonding[0].amount, Uint128::new(400));
+    // the share of the rewards of the unstaked tokens is paid right away,
+    // the rest stays with the contract until it is compounded
+    let rewards = suite.balance_of(&owner);
+    assert!(rewards > 0);
+    assert!(staker.pending_rewards.u128() > 0);
+    assert_eq!(suite.exchange_rate().total_supply, Uint128::new(600));
+
+    let err = suite
+        .execute(&owner, ExecuteMsg::Claim {}, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        ContractError::NothingToClaim {}.to_string()
+    );
+
+    // the unstaked funds stay delegated until the batch is submitted
+    let delegated = suite.delegated(&suite.validator_1) + suite.delegated(&suite.validator_2);
+    suite
+        .execute(&owner, ExecuteMsg::SubmitBatch {}, &[])
+        .unwrap();
+    assert_eq!(
+        suite.delegated(&suite.validator_1) + suite.delegated(&suite.validator_2),
+        delegated - 400
+    );
+    assert!(suite.staker(&owner).unbonding[0].release_at.is_some());
+
+    suite
+        .app
+        .update_block(|block| block.time = block.time.plus_seconds(UNBONDING_TIME));
+    suite.execute(&owner, ExecuteMsg::Claim {}, &[]).unwrap();
+    assert_eq!(suite.balance_of(&owner), rewards + 400);
+    assert!(suite.staker(&owner).unbonding.is_empty());
+}
+
+#[test]
+fn test_slash_during_unbonding() {
+    let mut suite = Suite::new();
+    let owner = suite.owner.clone();
+    let alice = MockApi::default().addr_make("alice");
+    let lst_denom = suite.lst_denom();
+    suite
+        .app
+        .sudo(SudoMsg::Bank(BankSudo::Mint {
+            to_address: alice.to_string(),
+            amount: coins(1_000, DENOM),
+        }))
+        .unwrap();
+
+    for staker in [&owner, &alice] {
+        suite
+            .execute(
+                staker,
+                ExecuteMsg::DelegateWeighted {},
+                &coins(1_000, DENOM),
+            )
+            .unwrap();
+    }
+    for staker in [&owner, &alice] {
+        suite
+            .execute(staker, ExecuteMsg::Unstake {}, &coins(400, &lst_denom))
+            .unwrap();
+    }
+    suite
+        .execute(&alice, ExecuteMsg::SubmitBatch {}, &[])
+        .unwrap();
+    suite
+        .app
+        .sudo(SudoMsg::Staking(StakingSudo::Slash {
+            validator: suite.validator_1.clone(),
+            percentage: Decimal::percent(50),
+        }))
+        .unwrap();
+    suite
+        .app
+        .update_block(|block| block.time = block.time.plus_seconds(UNBONDING_TIME));
+
+    // less than the recorded 800 comes back, and claiming first doesn't
+    // leave the other staker short
+    let returned = suite.balance();
+    assert!(returned < 800);
+    suite.execute(&owner, ExecuteMsg::Claim {}, &[]).unwrap();
+    assert_eq!(suite.balance_of(&owner), returned / 2);
+    suite.execute(&alice, ExecuteMsg::Claim {}, &[]).unwrap();
+    assert_eq!(suite.balance_of(&alice), returned - returned / 2);
+    assert_eq!(suite.balance(), 0);
+}
+
+#[test]
+fn test_compound() {
+    let mut suite = Suite::new();
+    let owner = suite.owner.clone();
+    let keeper = MockApi::default().addr_make("keeper");
+
+    suite
+        .execute(
+            &owner,
+            ExecuteMsg::DelegateWeighted {},
+            &coins(1_000, DENOM),
+        )
+        .unwrap();
+    suite
+        .app
+        .update_block(|block| block.time = block.time.plus_seconds(60 * 60 * 24 * 365));
+
+    suite
+        .execute(&keeper, ExecuteMsg::Compound {}, &[])
+        .unwrap();
+    let bounty = suite.balance_of(&keeper);
+    assert!(bounty > 0);
+    let restaked =
+        suite.delegated(&suite.validator_1) + suite.delegated(&suite.validator_2) - 1_000;
+    assert!(restaked > bounty);
+    assert_eq!(suite.balance(), 0);
+    assert_eq!(suite.staker(&owner).principal.u128(), 1_000 + restaked);
+    let rate = suite.exchange_rate();
+    assert_eq!(rate.total_supply, Uint128::new(1_000));
+    assert_eq!(rate.rate, Decimal::from_ratio(1_000 + restaked, 1_000u128));
+
+    let err = suite
+        .execute(&keeper, ExecuteMsg::Compound {}, &[])
+        .unwrap_err();
+    assert!(err
+        .root_cause()
+        .to_string()
+        .starts_with("Cannot compound again"));
+}
+
+#[test]
+fn test_stake_right_before_compound() {
+    let mut suite = Suite::new();
+    let owner = suite.owner.clone();
+    let alice = MockApi::default().addr_make("alice");
+    let keeper = MockApi::default().addr_make("keeper");
+    let lst_denom = suite.lst_denom();
+    suite
+        .app
+        .sudo(SudoMsg::Bank(BankSudo::Mint {
+            to_address: alice.to_string(),
+            amount: coins(1_000, DENOM),
+        }))
+        .unwrap();
+
+    suite
+        .execute(
+            &owner,
+            ExecuteMsg::DelegateWeighted {},
+            &coins(1_000, DENOM),
+        )
+        .unwrap();
+    suite
+        .app
+        .update_block(|block| block.time = block.time.plus_seconds(60 * 60 * 24 * 365));
+    let pending = suite.staker(&owner).pending_rewards.u128();
+    assert!(pending > 0);
+    assert_eq!(
+        suite.exchange_rate().rate,
+        Decimal::from_ratio(1_000 + pending, 1_000u128)
+    );
+
+    // the rewards earned so far are priced in, so alice gets fewer shares
+    suite
+        .execute(
+            &alice,
+            ExecuteMsg::DelegateWeighted {},
+            &coins(1_000, DENOM),
+        )
+        .unwrap();
+    let shares = suite
+        .app
+        .wrap()
+        .query_balance(&alice, &lst_denom)
+        .unwrap()
+        .amount
+        .u128();
+    assert_eq!(shares, 1_000 * 1_000 / (1_000 + pending));
+
+    suite
+        .execute(&keeper, ExecuteMsg::Compound {}, &[])
+        .unwrap();
+    let bounty = suite.balance_of(&keeper);
+    assert!(bounty > 0);
+    // the compound leaves alice with what she staked, less her part of the
+    // bounty, while the owner keeps the rewards earned before she came in
+    let alice_value = suite.staker(&alice).principal.u128();
+    assert!(alice_value <= 1_000);
+    assert!(alice_value + bounty >= 1_000);
+    assert!(suite.staker(&owner).principal.u128() + bounty >= 1_000 + pending);
+}
+
+#[test]
+fn test_sync_delegations_after_slash() {
+    let mut suite = Suite::new();
+    let owner = suite.owner.clone();
+
+    suite
+        .execute(
+            &owner,
+            ExecuteMsg::DelegateWeighted {},
+            &coins(1_000, DENOM),
+        )
+        .unwrap();
+    suite
+        .app
+        .sudo(SudoMsg::Staking(StakingSudo::Slash {
+            validator: suite.validator_1.clone(),
+            percentage: Decimal::percent(50),
+        }))
+        .unwrap();
+    assert_eq!(suite.delegated(&suite.validator_1), 250);
+    // the loss is priced in before the delegations are synced
+    assert_eq!(suite.staker(&owner).principal.u128(), 750);
+
+    let res = suite
+        .app
+        .execute_contract(
+            owner.clone(),
+            suite.contract.clone(),
+            &ExecuteMsg::SyncDelegations {},
+            &[],
+        )
+        .unwrap();
+    let event = res
+        .events
+        .iter()
+        .find(|e| e.ty == "wasm-slash_detected")
+        .unwrap();
+    assert!(event
+        .attributes
+        .iter()
+        .any(|a| a.key == "loss" && a.value == "250"));
+    assert_eq!(suite.staker(&owner).principal.u128(), 750);
+}
+
+#[test]
+fn test_unstake_after_slash() {
+    let mut suite = Suite::new();
+    let owner = suite.owner.clone();
+    let lst_denom = suite.lst_denom();
+
+    suite
+        .execute(
+            &owner,
+            ExecuteMsg::DelegateWeighted {},
+            &coins(1_000, DENOM),
+        )
+        .unwrap();
+    suite
+        .app
+        .sudo(SudoMsg::Staking(StakingSudo::Slash {
+            validator: suite.validator_1.clone(),
+            percentage: Decimal::percent(50),
+        }))
+        .unwrap();
+
+    // without a sync, the unstaked shares are still worth 25% less
+    suite
+        .execute(&owner, ExecuteMsg::Unstake {}, &coins(400, &lst_denom))
+        .unwrap();
+    let staker = suite.staker(&owner);
+    assert_eq!(staker.unbonding[0].amount.u128(), 300);
+    assert_eq!(staker.principal.u128(), 450);
+
+    suite
+        .execute(&owner, ExecuteMsg::SubmitBatch {}, &[])
+        .unwrap();
+    suite
+        .app
+        .update_block(|block| block.time = block.time.plus_seconds(UNBONDING_TIME));
+    suite.execute(&owner, ExecuteMsg::Claim {}, &[]).unwrap();
+    assert_eq!(suite.balance_of(&owner), 300);
+}
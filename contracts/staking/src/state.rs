@@ -0,0 +1,143 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal, Empty, Timestamp, Uint128, VoteOption};
+use cw_storage_plus::{Item, Map};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ValidatorWeight {
+    pub address: String,
+    /// Relative share of weighted delegations, must be non-zero.
+    pub weight: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+    // validators the contract is allowed to delegate to, in priority order
+    pub validators: Vec<ValidatorWeight>,
+    /// Seconds until undelegated funds are returned, matching the chain's unbonding time.
+    pub unbonding_period: u64,
+    /// Share of compounded rewards paid to whoever triggers the compound.
+    pub compound_bounty: Decimal,
+    /// Minimum number of seconds between two compounds.
+    pub compound_interval: u64,
+    /// Tokenfactory denom of the liquid staking token.
+    pub lst_denom: String,
+}
+
+impl Config {
+    pub fn has_validator(&self, address: &str) -> bool {
+        self.validators.iter().any(|v| v.address == address)
+    }
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub struct State {
+    /// Bond denom delegated on behalf of stakers, including compounded rewards.
+    pub total_staked: Uint128,
+    /// Supply of the liquid staking token, which is only minted and burned
+    /// by the contract.
+    pub total_shares: Uint128,
+    /// Rewards withdrawn to the contract that the next compound will restake.
+    pub idle_rewards: Uint128,
+}
+
+impl State {
+    /// The bond denom backing all shares. Rewards that wait to be compounded
+    /// already belong to the current holders, so whoever stakes or unstakes
+    /// before the next compound neither gains nor loses them. Rewards still on
+    /// the delegations have to be accrued into `idle_rewards` first.
+    pub fn total_value(&self) -> Uint128 {
+        self.total_staked + self.idle_rewards
+    }
+
+    /// The amount of the bond denom `shares` are worth, rounded down.
+    pub fn shares_to_amount(&self, shares: Uint128) -> Uint128 {
+        if self.total_shares.is_zero() {
+            return Uint128::zero();
+        }
+        shares.multiply_ratio(self.total_value(), self.total_shares)
+    }
+
+    /// The part of `shares_to_amount` made of idle rewards, rounded down.
+    pub fn shares_to_rewards(&self, shares: Uint128) -> Uint128 {
+        if self.total_shares.is_zero() {
+            return Uint128::zero();
+        }
+        shares.multiply_ratio(self.idle_rewards, self.total_shares)
+    }
+
+    /// The shares minted for staking `amount`, one per unit while nothing is staked.
+    pub fn amount_to_shares(&self, amount: Uint128) -> Uint128 {
+        let value = self.total_value();
+        if self.total_shares.is_zero() || value.is_zero() {
+            return amount;
+        }
+        amount.multiply_ratio(self.total_shares, value)
+    }
+
+    /// The bond denom backing one liquid staking token.
+    pub fn exchange_rate(&self) -> Decimal {
+        if self.total_shares.is_zero() {
+            return Decimal::one();
+        }
+        Decimal::from_ratio(self.total_value(), self.total_shares)
+    }
+}
+
+/// A staker's part of an unbonding batch.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct UnbondingEntry {
+    pub batch: u64,
+    pub amount: Uint128,
+}
+
+/// Unstaked funds that are undelegated together, so the contract stays under
+/// the chain's limit of unbonding entries per validator.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub struct Batch {
+    /// Sum of the unbonding entries in the batch.
+    pub requested: Uint128,
+    /// Bond denom backing the batch, what was undelegated once submitted
+    /// and what arrived once settled.
+    pub value: Uint128,
+    /// When the undelegations complete, `None` until the batch is submitted.
+    pub release_at: Option<Timestamp>,
+    /// Whether the unbonded funds have arrived in the contract's balance.
+    pub settled: bool,
+}
+
+pub const STATE: Item<State> = Item::new("state");
+pub const UNBONDING: Map<&Addr, Vec<UnbondingEntry>> = Map::new("unbonding");
+pub const BATCHES: Map<u64, Batch> = Map::new("batches");
+/// Id of the batch that collects new unstakes.
+pub const PENDING_BATCH: Item<u64> = Item::new("pending_batch");
+/// Number of batches created so far, used to hand out ids.
+pub const BATCH_COUNT: Item<u64> = Item::new("batch_count");
+pub const LAST_BATCH_SUBMIT: Item<Timestamp> = Item::new("last_batch_submit");
+pub const LAST_UNDELEGATE: Item<Timestamp> = Item::new("last_undelegate");
+/// Submitted batches that haven't been settled yet, keyed by release time and id.
+pub const UNSETTLED: Map<(u64, u64), Empty> = Map::new("unsettled");
+/// Bond denom of settled batches that is in the contract's balance but
+/// hasn't been claimed yet.
+pub const UNCLAIMED: Item<Uint128> = Item::new("unclaimed");
+
+/// Details of a compound kept around until its reward withdrawals have executed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct PendingCompound {
+    pub caller: Addr,
+    /// The contract's balance of the bond denom before withdrawing rewards.
+    pub balance_before: Uint128,
+}
+
+pub const PENDING_COMPOUND: Item<PendingCompound> = Item::new("pending_compound");
+pub const LAST_COMPOUND: Item<Timestamp> = Item::new("last_compound");
+
+/// Votes signaled by stakers, keyed by proposal id. They are weighted by the
+/// staker's liquid staking tokens when the contract casts its vote.
+pub const VOTE_SIGNALS: Map<(u64, &Addr), VoteOption> = Map::new("vote_signals");
+/// Number of stakers that signaled on each proposal.
+pub const VOTE_SIGNAL_COUNT: Map<u64, u32> = Map::new("vote_signal_count");
@@ -0,0 +1,110 @@
+use std::cmp::Reverse;
+
+use cosmwasm_std::Uint128;
+
+use crate::state::ValidatorWeight;
+
+/// Splits `amount` across `validators` proportionally to their weights. Shares
+/// are rounded down and the remainder goes to the first validator.
+pub fn split_by_weight(amount: Uint128, validators: &[ValidatorWeight]) -> Vec<(String, Uint128)> {
+    let total_weight: u128 = validators.iter().map(|v| u128::from(v.weight)).sum();
+    if total_weight == 0 {
+        return vec![];
+    }
+
+    let mut shares: Vec<(String, Uint128)> = validators
+        .iter()
+        .map(|v| {
+            (
+                v.address.clone(),
+                amount.multiply_ratio(v.weight, total_weight),
+            )
+        })
+        .collect();
+    let distributed: Uint128 = shares.iter().map(|(_, share)| *share).sum();
+    shares[0].1 += amount - distributed;
+
+    shares
+}
+
+/// Picks the undelegations that free up `amount` from `current` delegations,
+/// draining the largest delegations first. Returns less than `amount` when
+/// the delegations don't cover it.
+pub fn undelegations(current: &[(String, Uint128)], amount: Uint128) -> Vec<(String, Uint128)> {
+    let mut sorted = current.to_vec();
+    sorted.sort_by_key(|(_, delegated)| Reverse(*delegated));
+
+    let mut remaining = amount;
+    let mut picked = vec![];
+    for (validator, delegated) in sorted {
+        if remaining.is_zero() {
+            break;
+        }
+        let take = remaining.min(delegated);
+        if !take.is_zero() {
+            picked.push((validator, take));
+            remaining -= take;
+        }
+    }
+
+    picked
+}
+
+/// A redelegation of `amount` from `src` to `dst`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Move {
+    pub src: String,
+    pub dst: String,
+    pub amount: Uint128,
+}
+
+/// Computes the redelegations that bring `current` delegations, one entry per
+/// validator, to the weighted target split of their total. Delegations to validators outside of
+/// `validators` have a target of zero.
+pub fn rebalance_moves(current: &[(String, Uint128)], validators: &[ValidatorWeight]) -> Vec<Move> {
+    let total: Uint128 = current.iter().map(|(_, amount)| *amount).sum();
+    let targets = split_by_weight(total, validators);
+    let amount_of = |split: &[(String, Uint128)], address: &str| {
+        split
+            .iter()
+            .find(|(v, _)| v == address)
+            .map(|(_, amount)| *amount)
+            .unwrap_or_default()
+    };
+
+    let mut surpluses: Vec<(String, Uint128)> = current
+        .iter()
+        .filter_map(|(v, amount)| {
+            let target = amount_of(&targets, v);
+            (*amount > target).then(|| (v.clone(), *amount - target))
+        })
+        .collect();
+    let mut deficits: Vec<(String, Uint128)> = targets
+        .iter()
+        .filter_map(|(v, target)| {
+            let amount = amount_of(current, v);
+            (*target > amount).then(|| (v.clone(), *target - amount))
+        })
+        .collect();
+
+    let mut moves = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < surpluses.len() && j < deficits.len() {
+        let amount = surpluses[i].1.min(deficits[j].1);
+        moves.push(Move {
+            src: surpluses[i].0.clone(),
+            dst: deficits[j].0.clone(),
+            amount,
+        });
+        surpluses[i].1 -= amount;
+        deficits[j].1 -= amount;
+        if surpluses[i].1.is_zero() {
+            i += 1;
+        }
+        if deficits[j].1.is_zero() {
+            j += 1;
+        }
+    }
+
+    moves
+}
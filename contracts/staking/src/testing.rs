@@ -0,0 +1,1393 @@
+use cosmwasm_std::testing::{
+    message_info, mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage, StakingQuerier,
+};
+use cosmwasm_std::{
+    coin, coins, from_json, Addr, AnyMsg, BankMsg, Coin, CosmosMsg, Decimal, Deps, DepsMut,
+    DistributionMsg, FullDelegation, GovMsg, OwnedDeps, Reply, StakingMsg, SubMsgResponse,
+    SubMsgResult, Uint128, Validator, VoteOption, WeightedVoteOption,
+};
+use nibiru_std::proto::nibiru::tokenfactory::{MsgBurn, MsgCreateDenom, MsgMint};
+use nibiru_std::proto::NibiruProstMsg;
+use prost::Name;
+
+use crate::contract::{execute, instantiate, query, reply, COMPOUND_REPLY_ID, MAX_VOTE_SIGNALS};
+use crate::msg::{
+    ConfigResponse, DelegationResponse, DelegationsResponse, ExchangeRateResponse, ExecuteMsg,
+    InstantiateMsg, PendingRewardsResponse, QueryMsg, StakerResponse, UnbondingInfo, ValidatorInfo,
+    ValidatorRewards, ValidatorsResponse, VoteTallyResponse,
+};
+use crate::state::ValidatorWeight;
+use crate::strategy::{rebalance_moves, split_by_weight, undelegations, Move};
+use crate::ContractError;
+
+const VALIDATOR_1: &str = "nibivaloper1one";
+const VALIDATOR_2: &str = "nibivaloper1two";
+const VALIDATOR_3: &str = "nibivaloper1three";
+const UNBONDING_PERIOD: u64 = 21 * 24 * 60 * 60;
+const COMPOUND_INTERVAL: u64 = 60 * 60;
+
+fn validator(address: &str) -> Validator {
+    Validator::create(
+        address.to_string(),
+        Decimal::percent(5),
+        Decimal::percent(20),
+        Decimal::percent(1),
+    )
+}
+
+fn mock_dependencies_with_validators() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+    deps.querier.staking = StakingQuerier::new(
+        "unibi",
+        &[
+            validator(VALIDATOR_1),
+            validator(VALIDATOR_2),
+            validator(VALIDATOR_3),
+        ],
+        &[],
+    );
+    deps
+}
+
+fn lst_denom() -> String {
+    format!("tf/{}/stnibi", mock_env().contract.address)
+}
+
+fn any_msg<M: NibiruProstMsg + Name>(msg: M) -> CosmosMsg {
+    CosmosMsg::Any(AnyMsg {
+        type_url: M::type_url(),
+        value: msg.to_binary(),
+    })
+}
+
+fn mint_msg(amount: u128, recipient: &Addr) -> CosmosMsg {
+    any_msg(MsgMint {
+        sender: mock_env().contract.address.to_string(),
+        coin: Some(coin(amount, lst_denom()).into()),
+        mint_to: recipient.to_string(),
+    })
+}
+
+fn weighted(address: &str, weight: u64) -> ValidatorWeight {
+    ValidatorWeight {
+        address: address.to_string(),
+        weight,
+    }
+}
+
+fn setup(deps: DepsMut, validators: &[(&str, u64)]) -> Addr {
+    let owner = MockApi::default().addr_make("owner");
+    let res = instantiate(
+        deps,
+        mock_env(),
+        message_info(&owner, &[]),
+        InstantiateMsg {
+            validators: validators
+                .iter()
+                .map(|(address, weight)| weighted(address, *weight))
+                .collect(),
+            unbonding_period: UNBONDING_PERIOD,
+            compound_bounty: Decimal::percent(1),
+            compound_interval: COMPOUND_INTERVAL,
+            lst_subdenom: "stnibi".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        any_msg(MsgCreateDenom {
+            sender: mock_env().contract.address.to_string(),
+            subdenom: "stnibi".to_string(),
+        })
+    );
+    owner
+}
+
+fn configured_validators(deps: Deps) -> Vec<ValidatorWeight> {
+    let res: ConfigResponse =
+        from_json(query(deps, mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    res.validators
+}
+
+#[test]
+fn test_instantiate_validates_validators() {
+    let mut deps = mock_dependencies_with_validators();
+    let owner = MockApi::default().addr_make("owner");
+
+    let err = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&owner, &[]),
+        InstantiateMsg {
+            validators: vec![weighted(VALIDATOR_1, 1), weighted("nibivaloper1missing", 1)],
+            unbonding_period: UNBONDING_PERIOD,
+            compound_bounty: Decimal::percent(1),
+            compound_interval: COMPOUND_INTERVAL,
+            lst_subdenom: "stnibi".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::ValidatorNotFound { .. }));
+
+    let err = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&owner, &[]),
+        InstantiateMsg {
+            validators: vec![weighted(VALIDATOR_1, 1), weighted(VALIDATOR_1, 1)],
+            unbonding_period: UNBONDING_PERIOD,
+            compound_bounty: Decimal::percent(1),
+            compound_interval: COMPOUND_INTERVAL,
+            lst_subdenom: "stnibi".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::ValidatorAlreadyConfigured { .. }
+    ));
+
+    let err = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&owner, &[]),
+        InstantiateMsg {
+            validators: vec![weighted(VALIDATOR_1, 1)],
+            unbonding_period: UNBONDING_PERIOD,
+            compound_bounty: Decimal::percent(11),
+            compound_interval: COMPOUND_INTERVAL,
+            lst_subdenom: "stnibi".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::BountyTooHigh { .. }));
+
+    let err = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&owner, &[]),
+        InstantiateMsg {
+            validators: vec![weighted(VALIDATOR_1, 0)],
+            unbonding_period: UNBONDING_PERIOD,
+            compound_bounty: Decimal::percent(1),
+            compound_interval: COMPOUND_INTERVAL,
+            lst_subdenom: "stnibi".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::ZeroWeight { .. }));
+}
+
+#[test]
+fn test_delegate() {
+    let mut deps = mock_dependencies_with_validators();
+    setup(deps.as_mut(), &[(VALIDATOR_1, 1), (VALIDATOR_2, 1)]);
+    let sender = deps.api.addr_make("sender");
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&sender, &coins(100, "unibi")),
+        ExecuteMsg::Run {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages
+            .iter()
+            .map(|m| m.msg.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            mint_msg(100, &sender),
+            CosmosMsg::Staking(StakingMsg::Delegate {
+                validator: VALIDATOR_1.to_string(),
+                amount: coin(100, "unibi"),
+            }),
+        ]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&sender, &coins(100, "unibi")),
+        ExecuteMsg::Delegate {
+            validator: VALIDATOR_2.to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Staking(StakingMsg::Delegate {
+            validator: VALIDATOR_2.to_string(),
+            amount: coin(100, "unibi"),
+        })
+    );
+
+    // existing on chain is not enough, the validator must be configured
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&sender, &coins(100, "unibi")),
+        ExecuteMsg::Delegate {
+            validator: VALIDATOR_3.to_string(),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::ValidatorNotConfigured { .. }));
+}
+
+#[test]
+fn test_payment_must_be_bond_denom() {
+    let mut deps = mock_dependencies_with_validators();
+    setup(deps.as_mut(), &[(VALIDATOR_1, 1)]);
+    let sender = deps.api.addr_make("sender");
+
+    let mut run = |funds: &[Coin]| {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&sender, funds),
+            ExecuteMsg::Run {},
+        )
+        .unwrap_err()
+    };
+
+    assert!(matches!(run(&[]), ContractError::NoFunds {}));
+    assert!(matches!(run(&coins(0, "unibi")), ContractError::NoFunds {}));
+    assert!(matches!(
+        run(&[coin(100, "unibi"), coin(100, "uatom")]),
+        ContractError::MultipleDenoms {}
+    ));
+    match run(&coins(100, "uatom")) {
+        ContractError::WrongDenom { expected } => assert_eq!(expected, "unibi"),
+        err => panic!("unexpected error: {err}"),
+    }
+}
+
+#[test]
+fn test_manage_validators() {
+    let mut deps = mock_dependencies_with_validators();
+    let owner = setup(deps.as_mut(), &[(VALIDATOR_1, 1)]);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&MockApi::default().addr_make("anyone"), &[]),
+        ExecuteMsg::AddValidator {
+            validator: VALIDATOR_2.to_string(),
+            weight: 2,
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&owner, &[]),
+        ExecuteMsg::AddValidator {
+            validator: "nibivaloper1missing".to_string(),
+            weight: 1,
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::ValidatorNotFound { .. }));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&owner, &[]),
+        ExecuteMsg::AddValidator {
+            validator: VALIDATOR_2.to_string(),
+            weight: 2,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        configured_validators(deps.as_ref()),
+        vec![weighted(VALIDATOR_1, 1), weighted(VALIDATOR_2, 2)]
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&owner, &[]),
+        ExecuteMsg::RemoveValidator {
+            validator: VALIDATOR_1.to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        configured_validators(deps.as_ref()),
+        vec![weighted(VALIDATOR_2, 2)]
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&owner, &[]),
+        ExecuteMsg::RemoveValidator {
+            validator: VALIDATOR_1.to_string(),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::ValidatorNotConfigured { .. }));
+}
+
+#[test]
+fn test_split_by_weight() {
+    let validators = [
+        weighted(VALIDATOR_1, 1),
+        weighted(VALIDATOR_2, 1),
+        weighted(VALIDATOR_3, 1),
+    ];
+
+    // 100 / 3 leaves a remainder of 1 for the first validator
+    assert_eq!(
+        split_by_weight(Uint128::new(100), &validators),
+        vec![
+            (VALIDATOR_1.to_string(), Uint128::new(34)),
+            (VALIDATOR_2.to_string(), Uint128::new(33)),
+            (VALIDATOR_3.to_string(), Uint128::new(33)),
+        ]
+    );
+    assert_eq!(
+        split_by_weight(
+            Uint128::new(1),
+            &[weighted(VALIDATOR_1, 1), weighted(VALIDATOR_2, 5)]
+        ),
+        vec![
+            (VALIDATOR_1.to_string(), Uint128::new(1)),
+            (VALIDATOR_2.to_string(), Uint128::zero()),
+        ]
+    );
+}
+
+#[test]
+fn test_rebalance_moves() {
+    let validators = [weighted(VALIDATOR_1, 1), weighted(VALIDATOR_2, 3)];
+    let current = [
+        (VALIDATOR_1.to_string(), Uint128::new(60)),
+        (VALIDATOR_2.to_string(), Uint128::new(20)),
+        // no longer configured, so everything moves out
+        (VALIDATOR_3.to_string(), Uint128::new(20)),
+    ];
+
+    assert_eq!(
+        rebalance_moves(&current, &validators),
+        vec![
+            Move {
+                src: VALIDATOR_1.to_string(),
+                dst: VALIDATOR_2.to_string(),
+                amount: Uint128::new(35),
+            },
+            Move {
+                src: VALIDATOR_3.to_string(),
+                dst: VALIDATOR_2.to_string(),
+                amount: Uint128::new(20),
+            },
+        ]
+    );
+
+    // already balanced
+    let current = [
+        (VALIDATOR_1.to_string(), Uint128::new(25)),
+        (VALIDATOR_2.to_string(), Uint128::new(75)),
+    ];
+    assert_eq!(rebalance_moves(&current, &validators), vec![]);
+}
+
+#[test]
+fn test_delegate_weighted() {
+    let mut deps = mock_dependencies_with_validators();
+    setup(deps.as_mut(), &[(VALIDATOR_1, 2), (VALIDATOR_2, 1)]);
+    let sender = deps.api.addr_make("sender");
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&sender, &coins(100, "unibi")),
+        ExecuteMsg::DelegateWeighted {},
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.messages
+            .iter()
+            .map(|m| m.msg.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            mint_msg(100, &sender),
+            CosmosMsg::Staking(StakingMsg::Delegate {
+                validator: VALIDATOR_1.to_string(),
+                amount: coin(67, "unibi"),
+            }),
+            CosmosMsg::Staking(StakingMsg::Delegate {
+                validator: VALIDATOR_2.to_string(),
+                amount: coin(33, "unibi"),
+            }),
+        ]
+    );
+}
+
+#[test]
+fn test_rebalance() {
+    let mut deps = mock_dependencies_with_validators();
+    let owner = setup(deps.as_mut(), &[(VALIDATOR_1, 1), (VALIDATOR_2, 1)]);
+    let contract = mock_env().contract.address;
+    deps.querier.staking = StakingQuerier::new(
+        "unibi",
+        &[validator(VALIDATOR_1), validator(VALIDATOR_2)],
+        &[FullDelegation::create(
+            contract,
+            VALIDATOR_1.to_string(),
+            coin(100, "unibi"),
+            coin(100, "unibi"),
+            vec![],
+        )],
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&MockApi::default().addr_make("anyone"), &[]),
+        ExecuteMsg::Rebalance {},
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&owner, &[]),
+        ExecuteMsg::Rebalance {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages
+            .iter()
+            .map(|m| m.msg.clone())
+            .collect::<Vec<_>>(),
+        vec![CosmosMsg::Staking(StakingMsg::Redelegate {
+            src_validator: VALIDATOR_1.to_string(),
+            dst_validator: VALIDATOR_2.to_string(),
+            amount: coin(50, "unibi"),
+        })]
+    );
+}
+
+#[test]
+fn test_undelegations() {
+    let current = [
+        (VALIDATOR_1.to_string(), Uint128::new(30)),
+        (VALIDATOR_2.to_string(), Uint128::new(50)),
+        (VALIDATOR_3.to_string(), Uint128::new(20)),
+    ];
+
+    assert_eq!(
+        undelegations(&current, Uint128::new(60)),
+        vec![
+            (VALIDATOR_2.to_string(), Uint128::new(50)),
+            (VALIDATOR_1.to_string(), Uint128::new(10)),
+        ]
+    );
+    // capped at what is delegated
+    assert_eq!(
+        undelegations(&current, Uint128::new(500))
+            .iter()
+            .map(|(_, amount)| amount.u128())
+            .sum::<u128>(),
+        100
+    );
+}
+
+fn staker(deps: Deps, address: &Addr) -> StakerResponse {
+    from_json(
+        query(
+            deps,
+            mock_env(),
+            QueryMsg::Staker {
+                address: address.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_unstake_and_claim() {
+    let mut deps = mock_dependencies_with_validators();
+    setup(deps.as_mut(), &[(VALIDATOR_1, 1)]);
+    let alice = deps.api.addr_make("alice");
+    let bob = deps.api.addr_make("bob");
+    let contract = mock_env().contract.address;
+    let delegation = |amount: u128, rewards: u128| {
+        StakingQuerier::new(
+            "unibi",
+            &[validator(VALIDATOR_1)],
+            &[FullDelegation::create(
+                contract.clone(),
+                VALIDATOR_1.to_string(),
+                coin(amount, "unibi"),
+                coin(amount, "unibi"),
+                coins(rewards, "unibi"),
+            )],
+        )
+    };
+
+    for (sender, amount, delegated) in [(&alice, 300, 300), (&bob, 100, 400)] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(sender, &coins(amount, "unibi")),
+            ExecuteMsg::Delegate {
+                validator: VALIDATOR_1.to_string(),
+            },
+        )
+        .unwrap();
+        deps.querier
+            .bank
+            .update_balance(sender, coins(amount, lst_denom()));
+        deps.querier.staking = delegation(delegated, 0);
+    }
+
+    // 40 unibi of rewards have accumulated on the delegation
+    deps.querier.staking = delegation(400, 40);
+    assert_eq!(
+        staker(deps.as_ref(), &alice),
+        StakerResponse {
+            principal: Uint128::new(300),
+            shares: Uint128::new(300),
+            pending_rewards: Uint128::new(30),
+            unbonding: vec![],
+        }
+    );
+
+    // unstaking takes the liquid staking token, not the bond denom
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&alice, &coins(200, "unibi")),
+        ExecuteMsg::Unstake {},
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::WrongDenom { .. }));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&alice, &coins(200, lst_denom())),
+        ExecuteMsg::Unstake {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages
+            .iter()
+            .map(|m| m.msg.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            CosmosMsg::Distribution(DistributionMsg::WithdrawDelegatorReward {
+                validator: VALIDATOR_1.to_string(),
+            }),
+            any_msg(MsgBurn {
+                sender: contract.to_string(),
+                coin: Some(coin(200, lst_denom()).into()),
+                burn_from: contract.to_string(),
+            }),
+            // alice's share of the rewards doesn't need to unbond
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: alice.to_string(),
+                amount: coins(20, "unibi"),
+            }),
+        ]
+    );
+
+    // the rest of the rewards has been withdrawn and waits to be compounded
+    // for the remaining holders
+    deps.querier.staking = delegation(400, 0);
+    deps.querier
+        .bank
+        .update_balance(&alice, coins(100, lst_denom()));
+    assert_eq!(
+        staker(deps.as_ref(), &alice),
+        StakerResponse {
+            principal: Uint128::new(100),
+            shares: Uint128::new(100),
+            pending_rewards: Uint128::new(10),
+            unbonding: vec![UnbondingInfo {
+                batch: 1,
+                amount: Uint128::new(200),
+                release_at: None,
+            }],
+        }
+    );
+    assert_eq!(
+        staker(deps.as_ref(), &bob).pending_rewards,
+        Uint128::new(10)
+    );
+
+    // bob's unstake joins the same batch
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&bob, &coins(100, lst_denom())),
+        ExecuteMsg::Unstake {},
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&alice, &[]),
+        ExecuteMsg::Claim {},
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::NothingToClaim {}));
+
+    // the batch is undelegated at once
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&bob, &[]),
+        ExecuteMsg::SubmitBatch {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages
+            .iter()
+            .map(|m| m.msg.clone())
+            .collect::<Vec<_>>(),
+        vec![CosmosMsg::Staking(StakingMsg::Undelegate {
+            validator: VALIDATOR_1.to_string(),
+            amount: coin(300, "unibi"),
+        })]
+    );
+    let release_at = mock_env().block.time.plus_seconds(UNBONDING_PERIOD);
+    assert_eq!(
+        staker(deps.as_ref(), &alice).unbonding,
+        vec![UnbondingInfo {
+            batch: 1,
+            amount: Uint128::new(200),
+            release_at: Some(release_at),
+        }]
+    );
+
+    // the next batch can only be submitted a fifth of the unbonding period later
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&bob, &[]),
+        ExecuteMsg::SubmitBatch {},
+    )
+    .unwrap_err();
+    let next = mock_env().block.time.plus_seconds(UNBONDING_PERIOD / 5);
+    assert!(matches!(err, ContractError::UnbondingTooSoon { next: n } if n == next));
+    let mut env = mock_env();
+    env.block.time = next;
+    let err = execute(
+        deps.as_mut(),
+        env,
+        message_info(&bob, &[]),
+        ExecuteMsg::SubmitBatch {},
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::EmptyBatch {}));
+
+    // the unbonded funds arrive next to the remaining rewards
+    deps.querier
+        .bank
+        .update_balance(&contract, coins(310, "unibi"));
+    let mut env = mock_env();
+    env.block.time = release_at;
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        message_info(&alice, &[]),
+        ExecuteMsg::Claim {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: alice.to_string(),
+            amount: coins(200, "unibi"),
+        })
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        env,
+        message_info(&alice, &[]),
+        ExecuteMsg::Claim {},
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::NothingToClaim {}));
+}
+
+#[test]
+fn test_claim_before_unbonded_funds_arrive() {
+    let mut deps = mock_dependencies_with_validators();
+    setup(deps.as_mut(), &[(VALIDATOR_1, 1)]);
+    let alice = deps.api.addr_make("alice");
+    let bob = deps.api.addr_make("bob");
+    let carol = deps.api.addr_make("carol");
+    let contract = mock_env().contract.address;
+    deps.querier.staking = StakingQuerier::new(
+        "unibi",
+        &[validator(VALIDATOR_1)],
+        &[FullDelegation::create(
+            contract.clone(),
+            VALIDATOR_1.to_string(),
+            coin(250, "unibi"),
+            coin(250, "unibi"),
+            vec![],
+        )],
+    );
+    let at = |seconds: u64| {
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(seconds);
+        env
+    };
+    let interval = UNBONDING_PERIOD / 5;
+
+    for (sender, amount) in [(&alice, 100), (&carol, 100), (&bob, 50)] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(sender, &coins(amount, "unibi")),
+            ExecuteMsg::Delegate {
+                validator: VALIDATOR_1.to_string(),
+            },
+        )
+        .unwrap();
+    }
+    // alice and carol unbond in the first batch, bob in the second
+    for (batch, time) in [
+        (vec![(&alice, 100), (&carol, 100)], 0),
+        (vec![(&bob, 50)], interval),
+    ] {
+        for (sender, amount) in batch {
+            execute(
+                deps.as_mut(),
+                at(time),
+                message_info(sender, &coins(amount, lst_denom())),
+                ExecuteMsg::Unstake {},
+            )
+            .unwrap();
+        }
+        execute(
+            deps.as_mut(),
+            at(time),
+            message_info(&bob, &[]),
+            ExecuteMsg::SubmitBatch {},
+        )
+        .unwrap();
+    }
+
+    // the first batch has arrived, carol hasn't claimed her part yet
+    deps.querier
+        .bank
+        .update_balance(&contract, coins(200, "unibi"));
+    execute(
+        deps.as_mut(),
+        at(UNBONDING_PERIOD),
+        message_info(&alice, &[]),
+        ExecuteMsg::Claim {},
+    )
+    .unwrap();
+    deps.querier
+        .bank
+        .update_balance(&contract, coins(100, "unibi"));
+
+    // the second batch matures in this block, but its funds only arrive at
+    // the end of it, so bob can't claim carol's funds as his own
+    let err = execute(
+        deps.as_mut(),
+        at(UNBONDING_PERIOD + interval),
+        message_info(&bob, &[]),
+        ExecuteMsg::Claim {},
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::UnbondingNotReceived {}));
+    assert_eq!(staker(deps.as_ref(), &bob).unbonding.len(), 1);
+
+    deps.querier
+        .bank
+        .update_balance(&contract, coins(150, "unibi"));
+    for (sender, amount) in [(&bob, 50), (&carol, 100)] {
+        let res = execute(
+            deps.as_mut(),
+            at(UNBONDING_PERIOD + interval),
+            message_info(sender, &[]),
+            ExecuteMsg::Claim {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: sender.to_string(),
+                amount: coins(amount, "unibi"),
+            })
+        );
+    }
+}
+
+#[test]
+fn test_staking_queries() {
+    let mut deps = mock_dependencies_with_validators();
+    setup(deps.as_mut(), &[(VALIDATOR_1, 2), (VALIDATOR_3, 1)]);
+    let contract = mock_env().contract.address;
+    let delegation = FullDelegation::create(
+        contract.clone(),
+        VALIDATOR_1.to_string(),
+        coin(100, "unibi"),
+        coin(100, "unibi"),
+        vec![coin(7, "unibi"), coin(3, "uatom")],
+    );
+    // the third validator has left the active set since
+    deps.querier.staking = StakingQuerier::new(
+        "unibi",
+        &[validator(VALIDATOR_1), validator(VALIDATOR_2)],
+        std::slice::from_ref(&delegation),
+    );
+    let query = |msg: QueryMsg| query(deps.as_ref(), mock_env(), msg).unwrap();
+
+    let res: DelegationsResponse = from_json(query(QueryMsg::Delegations {})).unwrap();
+    assert_eq!(res.delegations.len(), 1);
+    assert_eq!(res.delegations[0].validator, VALIDATOR_1);
+    assert_eq!(res.delegations[0].amount, coin(100, "unibi"));
+
+    let res: DelegationResponse = from_json(query(QueryMsg::Delegation {
+        validator: VALIDATOR_1.to_string(),
+    }))
+    .unwrap();
+    assert_eq!(res.delegation, Some(delegation));
+    let res: DelegationResponse = from_json(query(QueryMsg::Delegation {
+        validator: VALIDATOR_2.to_string(),
+    }))
+    .unwrap();
+    assert_eq!(res.delegation, None);
+
+    let res: ValidatorsResponse = from_json(query(QueryMsg::Validators {})).unwrap();
+    assert_eq!(
+        res.validators,
+        vec![
+            ValidatorInfo {
+                address: VALIDATOR_1.to_string(),
+                weight: 2,
+                validator: Some(validator(VALIDATOR_1)),
+            },
+            ValidatorInfo {
+                address: VALIDATOR_3.to_string(),
+                weight: 1,
+                validator: None,
+            },
+        ]
+    );
+
+    // only rewards in the bond denom are counted
+    let res: PendingRewardsResponse = from_json(query(QueryMsg::PendingRewards {})).unwrap();
+    assert_eq!(
+        res,
+        PendingRewardsResponse {
+            rewards: vec![ValidatorRewards {
+                validator: VALIDATOR_1.to_string(),
+                amount: coin(7, "unibi"),
+            }],
+            total: coin(7, "unibi"),
+        }
+    );
+}
+
+#[test]
+fn test_compound() {
+    let mut deps = mock_dependencies_with_validators();
+    setup(deps.as_mut(), &[(VALIDATOR_1, 1), (VALIDATOR_2, 1)]);
+    let alice = deps.api.addr_make("alice");
+    let keeper = deps.api.addr_make("keeper");
+    let contract = mock_env().contract.address;
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&alice, &coins(1_000, "unibi")),
+        ExecuteMsg::DelegateWeighted {},
+    )
+    .unwrap();
+    deps.querier
+        .bank
+        .update_balance(&alice, coins(1_000, lst_denom()));
+    deps.querier.staking = StakingQuerier::new(
+        "unibi",
+        &[validator(VALIDATOR_1), validator(VALIDATOR_2)],
+        &[VALIDATOR_1, VALIDATOR_2].map(|v| {
+            FullDelegation::create(
+                contract.clone(),
+                v.to_string(),
+                coin(500, "unibi"),
+                coin(500, "unibi"),
+                coins(100, "unibi"),
+            )
+        }),
+    );
+    deps.querier
+        .bank
+        .update_balance(&contract, coins(5, "unibi"));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&keeper, &[]),
+        ExecuteMsg::Compound {},
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(res.messages[0].id, 0);
+    assert_eq!(res.messages[1].id, COMPOUND_REPLY_ID);
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Distribution(DistributionMsg::WithdrawDelegatorReward {
+            validator: VALIDATOR_2.to_string(),
+        })
+    );
+
+    // both withdrawals have landed by the time the reply runs
+    deps.querier
+        .bank
+        .update_balance(&contract, coins(205, "unibi"));
+    #[allow(deprecated)]
+    let res = reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: COMPOUND_REPLY_ID,
+            payload: Default::default(),
+            gas_used: 0,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+                msg_responses: vec![],
+            }),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages
+            .iter()
+            .map(|m| m.msg.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            CosmosMsg::Staking(StakingMsg::Delegate {
+                validator: VALIDATOR_1.to_string(),
+                amount: coin(99, "unibi"),
+            }),
+            CosmosMsg::Staking(StakingMsg::Delegate {
+                validator: VALIDATOR_2.to_string(),
+                amount: coin(99, "unibi"),
+            }),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: keeper.to_string(),
+                amount: coins(2, "unibi"),
+            }),
+        ]
+    );
+    // the restaked rewards count toward alice's principal
+    deps.querier
+        .bank
+        .update_balance(&contract, coins(5, "unibi"));
+    deps.querier.staking = StakingQuerier::new(
+        "unibi",
+        &[validator(VALIDATOR_1), validator(VALIDATOR_2)],
+        &[VALIDATOR_1, VALIDATOR_2].map(|v| {
+            FullDelegation::create(
+                contract.clone(),
+                v.to_string(),
+                coin(599, "unibi"),
+                coin(599, "unibi"),
+                vec![],
+            )
+        }),
+    );
+    assert_eq!(staker(deps.as_ref(), &alice).principal, Uint128::new(1_198));
+    let res: ExchangeRateResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::ExchangeRate {}).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        ExchangeRateResponse {
+            lst_denom: lst_denom(),
+            rate: Decimal::permille(1_198),
+            total_staked: Uint128::new(1_198),
+            total_supply: Uint128::new(1_000),
+        }
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&keeper, &[]),
+        ExecuteMsg::Compound {},
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::CompoundTooSoon { .. }));
+}
+
+#[test]
+fn test_owner_vote() {
+    let mut deps = mock_dependencies_with_validators();
+    let owner = setup(deps.as_mut(), &[(VALIDATOR_1, 1)]);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&MockApi::default().addr_make("anyone"), &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 1,
+            option: VoteOption::Yes,
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&owner, &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 1,
+            option: VoteOption::NoWithVeto,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Gov(GovMsg::Vote {
+            proposal_id: 1,
+            option: VoteOption::NoWithVeto,
+        })
+    );
+
+    let weighted = |option, percent| WeightedVoteOption {
+        option,
+        weight: Decimal::percent(percent),
+    };
+    for options in [
+        vec![weighted(VoteOption::Yes, 60), weighted(VoteOption::No, 30)],
+        vec![weighted(VoteOption::Yes, 50), weighted(VoteOption::Yes, 50)],
+        vec![weighted(VoteOption::Yes, 100), weighted(VoteOption::No, 0)],
+    ] {
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&owner, &[]),
+            ExecuteMsg::VoteWeighted {
+                proposal_id: 1,
+                options,
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidVoteWeights {}));
+    }
+
+    let options = vec![
+        weighted(VoteOption::Yes, 65),
+        weighted(VoteOption::Abstain, 35),
+    ];
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&owner, &[]),
+        ExecuteMsg::VoteWeighted {
+            proposal_id: 1,
+            options: options.clone(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Gov(GovMsg::VoteWeighted {
+            proposal_id: 1,
+            options,
+        })
+    );
+}
+
+#[test]
+fn test_signaled_vote() {
+    let mut deps = mock_dependencies_with_validators();
+    let owner = setup(deps.as_mut(), &[(VALIDATOR_1, 1)]);
+    let api = MockApi::default();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&api.addr_make("outsider"), &[]),
+        ExecuteMsg::SignalVote {
+            proposal_id: 7,
+            option: VoteOption::Yes,
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::NotStaker {}));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&owner, &[]),
+        ExecuteMsg::CastSignaledVote { proposal_id: 7 },
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::NoVoteSignals { proposal_id: 7 }
+    ));
+
+    for (name, amount, option) in [
+        ("alice", 100, VoteOption::Yes),
+        ("bob", 100, VoteOption::No),
+        ("carol", 100, VoteOption::No),
+    ] {
+        let staker = api.addr_make(name);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&staker, &coins(amount, "unibi")),
+            ExecuteMsg::Run {},
+        )
+        .unwrap();
+        deps.querier
+            .bank
+            .update_balance(&staker, coins(amount, lst_denom()));
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&staker, &[]),
+            ExecuteMsg::SignalVote {
+                proposal_id: 7,
+                option,
+            },
+        )
+        .unwrap();
+    }
+
+    // a third each can't be represented exactly, so the larger side absorbs the rounding
+    let tally: VoteTallyResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::VoteTally { proposal_id: 7 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        tally.tally,
+        vec![
+            (VoteOption::Yes, Uint128::new(100)),
+            (VoteOption::No, Uint128::new(200)),
+        ]
+    );
+    let options = vec![
+        WeightedVoteOption {
+            option: VoteOption::Yes,
+            weight: Decimal::from_ratio(1u128, 3u128),
+        },
+        WeightedVoteOption {
+            option: VoteOption::No,
+            weight: Decimal::one() - Decimal::from_ratio(1u128, 3u128),
+        },
+    ];
+    assert_eq!(tally.options, options);
+
+    // the contract has a single vote, so stakers can't override each other
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&api.addr_make("alice"), &[]),
+        ExecuteMsg::CastSignaledVote { proposal_id: 7 },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&owner, &[]),
+        ExecuteMsg::CastSignaledVote { proposal_id: 7 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Gov(GovMsg::VoteWeighted {
+            proposal_id: 7,
+            options,
+        })
+    );
+}
+
+#[test]
+fn test_signaled_vote_limits() {
+    let mut deps = mock_dependencies_with_validators();
+    setup(deps.as_mut(), &[(VALIDATOR_1, 1)]);
+    let api = MockApi::default();
+
+    let signal = |deps: DepsMut, staker: &Addr, option: VoteOption| {
+        execute(
+            deps,
+            mock_env(),
+            message_info(staker, &[]),
+            ExecuteMsg::SignalVote {
+                proposal_id: 7,
+                option,
+            },
+        )
+    };
+
+    // a share too small to show up in the weights is left out of the vote
+    let whale = api.addr_make("whale");
+    let minnow = api.addr_make("minnow");
+    for (staker, amount, option) in [
+        (&whale, 10u128.pow(19), VoteOption::No),
+        (&minnow, 1, VoteOption::Yes),
+    ] {
+        deps.querier
+            .bank
+            .update_balance(staker, coins(amount, lst_denom()));
+        signal(deps.as_mut(), staker, option).unwrap();
+    }
+    let tally: VoteTallyResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::VoteTally { proposal_id: 7 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(tally.tally.len(), 2);
+    assert_eq!(
+        tally.options,
+        vec![WeightedVoteOption {
+            option: VoteOption::No,
+            weight: Decimal::one(),
+        }]
+    );
+
+    for i in 2..MAX_VOTE_SIGNALS {
+        let staker = api.addr_make(&format!("staker{i}"));
+        deps.querier
+            .bank
+            .update_balance(&staker, coins(1, lst_denom()));
+        signal(deps.as_mut(), &staker, VoteOption::Abstain).unwrap();
+    }
+
+    let late = api.addr_make("late");
+    deps.querier
+        .bank
+        .update_balance(&late, coins(1, lst_denom()));
+    let err = signal(deps.as_mut(), &late, VoteOption::Yes).unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::TooManyVoteSignals {
+            proposal_id: 7,
+            max: MAX_VOTE_SIGNALS,
+        }
+    ));
+
+    // stakers that already signaled can still change their mind
+    signal(deps.as_mut(), &minnow, VoteOption::No).unwrap();
+
+    // dust signals can't lock the others out: once the minnow has left, a
+    // staker with more takes its place
+    deps.querier
+        .bank
+        .update_balance(&minnow, coins(0, lst_denom()));
+    let res = signal(deps.as_mut(), &late, VoteOption::Yes).unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "replaced" && a.value == minnow.as_str()));
+    let err = signal(deps.as_mut(), &minnow, VoteOption::No).unwrap_err();
+    assert!(matches!(err, ContractError::NotStaker {}));
+    let tally: VoteTallyResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::VoteTally { proposal_id: 7 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(tally.tally.contains(&(VoteOption::Yes, Uint128::new(1))));
+}
+
+#[test]
+fn test_sync_delegations() {
+    let mut deps = mock_dependencies_with_validators();
+    setup(deps.as_mut(), &[(VALIDATOR_1, 1), (VALIDATOR_2, 1)]);
+    let alice = deps.api.addr_make("alice");
+    let bob = deps.api.addr_make("bob");
+    let contract = mock_env().contract.address;
+
+    let with_delegations = |amounts: [u128; 2]| {
+        StakingQuerier::new(
+            "unibi",
+            &[validator(VALIDATOR_1), validator(VALIDATOR_2)],
+            &[
+                FullDelegation::create(
+                    contract.clone(),
+                    VALIDATOR_1.to_string(),
+                    coin(amounts[0], "unibi"),
+                    coin(amounts[0], "unibi"),
+                    vec![],
+                ),
+                FullDelegation::create(
+                    contract.clone(),
+                    VALIDATOR_2.to_string(),
+                    coin(amounts[1], "unibi"),
+                    coin(amounts[1], "unibi"),
+                    vec![],
+                ),
+            ],
+        )
+    };
+
+    for (sender, amount, delegated) in [(&alice, 600, 300), (&bob, 400, 500)] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(sender, &coins(amount, "unibi")),
+            ExecuteMsg::DelegateWeighted {},
+        )
+        .unwrap();
+        deps.querier
+            .bank
+            .update_balance(sender, coins(amount, lst_denom()));
+        deps.querier.staking = with_delegations([delegated, delegated]);
+    }
+
+    // nothing lost yet
+    deps.querier.staking = with_delegations([500, 500]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&alice, &[]),
+        ExecuteMsg::SyncDelegations {},
+    )
+    .unwrap();
+    assert!(res.events.is_empty());
+
+    // the first validator was slashed by 20%
+    deps.querier.staking = with_delegations([400, 500]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&alice, &[]),
+        ExecuteMsg::SyncDelegations {},
+    )
+    .unwrap();
+    assert_eq!(res.events.len(), 1);
+    assert_eq!(res.events[0].ty, "slash_detected");
+    assert_eq!(res.events[0].attributes[0].value, "100");
+
+    assert_eq!(staker(deps.as_ref(), &alice).principal, Uint128::new(540));
+    assert_eq!(staker(deps.as_ref(), &bob).principal, Uint128::new(360));
+}
@@ -1,6 +1,6 @@
 use cosmwasm_std::{
-    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError,
-    StdResult,
+    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response,
+    StdError, StdResult,
 };
 use cw2::set_contract_version;
 
@@ -11,25 +11,37 @@ use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
 const CONTRACT_NAME: &str = "crates.io:counter";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+pub const WASTE_REPLY_ID: u64 = 1;
+pub const GAS_LIMIT_REPLY_ID: u64 = 2;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
-    _msg: InstantiateMsg,
+    msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    if let Some(work) = &msg.work {
+        execute::perform(deps.storage, work);
+    }
 
-    Ok(Response::new()
+    let res = Response::new()
         .add_attribute("method", "instantiate")
-        .add_attribute("owner", info.sender))
+        .add_attribute("owner", info.sender)
+        .add_attribute("work", msg.work.is_some().to_string());
+    #[cfg(feature = "large-code")]
+    let res = res
+        .add_attribute("blob_size", crate::large_code::BLOB_SIZE.to_string())
+        .add_attribute("blob_byte", crate::large_code::last_byte().to_string());
+    Ok(res)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
-    _deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
@@ -37,10 +49,491 @@ pub fn execute(
             "arbitrary revert".to_string(),
         ))),
         ExecuteMsg::NoGas {} => Ok(Response::new().add_attribute("method", "no_gas")),
+        ExecuteMsg::BurnCpu { iterations, revert } => {
+            execute::finish(execute::burn_cpu(iterations), revert)
+        }
+        ExecuteMsg::BurnStorageWrites {
+            count,
+            value_size,
+            revert,
+        } => execute::finish(
+            execute::burn_storage_writes(deps, count, value_size),
+            revert,
+        ),
+        ExecuteMsg::BurnStorageReads { count, revert } => {
+            execute::finish(execute::burn_storage_reads(deps.as_ref(), count), revert)
+        }
+        ExecuteMsg::BurnMemory { bytes, revert } => {
+            execute::finish(execute::burn_memory(bytes), revert)
+        }
+        ExecuteMsg::WasteThenRevert { work } => execute::waste_then_revert(deps, work),
+        ExecuteMsg::WasteInSubmsg { work, reply_on } => {
+            execute::waste_in_submsg(env, work, reply_on)
+        }
+        ExecuteMsg::SelfQuery { count, iterations } => {
+            execute::self_query(deps.as_ref(), env, count, iterations)
+        }
+        ExecuteMsg::EmitEvents {
+            events,
+            attributes_per_event,
+            value_len,
+        } => Ok(execute::emit_events(
+            events,
+            attributes_per_event,
+            value_len,
+        )),
+        ExecuteMsg::LargeData { bytes } => Ok(execute::large_data(bytes)),
+        ExecuteMsg::CallWithGasLimit {
+            target,
+            msg,
+            gas_limit,
+        } => execute::call_with_gas_limit(deps, info, target, msg, gas_limit),
+        ExecuteMsg::FillStorage { keys, value_size } => {
+            execute::fill_storage(deps, keys, value_size)
+        }
+        ExecuteMsg::ClearStorage { limit } => execute::clear_storage(deps, limit),
+        ExecuteMsg::Secp256k1Verify {
+            count,
+            message_hash,
+            signature,
+            public_key,
+        } => execute::secp256k1_verify(deps.as_ref(), count, message_hash, signature, public_key),
+        ExecuteMsg::Secp256k1RecoverPubkey {
+            count,
+            message_hash,
+            signature,
+            recovery_param,
+        } => execute::secp256k1_recover_pubkey(
+            deps.as_ref(),
+            count,
+            message_hash,
+            signature,
+            recovery_param,
+        ),
+        ExecuteMsg::Ed25519BatchVerify {
+            count,
+            messages,
+            signatures,
+            public_keys,
+        } => execute::ed25519_batch_verify(deps.as_ref(), count, messages, signatures, public_keys),
+        ExecuteMsg::Sha256 {
+            iterations,
+            input_size,
+        } => Ok(execute::sha256(iterations, input_size)),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        WASTE_REPLY_ID => execute::record_reply(deps, msg),
+        GAS_LIMIT_REPLY_ID => execute::record_gas_limited_failure(deps, msg),
+        id => Err(ContractError::UnknownReplyId { id }),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(_deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {}
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::LastReply {} => to_json_binary(&query::query_last_reply(deps)?),
+        QueryMsg::ExpensiveQuery { iterations } => {
+            to_json_binary(&query::query_expensive(iterations))
+        }
+        QueryMsg::LargeResponse { bytes } => to_json_binary(&query::query_large_response(bytes)),
+        QueryMsg::GasLimitedCall {} => to_json_binary(&query::query_gas_limited_call(deps)?),
+        QueryMsg::StorageStats {} => to_json_binary(&query::query_storage_stats(deps)?),
+    }
+}
+
+pub mod execute {
+    use std::hint::black_box;
+
+    use cosmwasm_std::{Event, HexBinary, ReplyOn, Storage, SubMsg, SubMsgResult, WasmMsg};
+    use sha2::{Digest, Sha256};
+
+    use crate::msg::{ExpensiveQueryResponse, Work};
+    use crate::state::{
+        burn_key, fill_key, GasLimitedCall, ReplyRecord, FILLED_KEYS, GAS_LIMITED_CALL, LAST_REPLY,
+    };
+
+    use super::*;
+
+    /// Fails after the work is done when `revert` is set, so the gas is
+    /// still charged while every write is rolled back.
+    pub fn finish(res: Response, revert: bool) -> Result<Response, ContractError> {
+        if revert {
+            return Err(ContractError::Reverted {});
+        }
+        Ok(res)
+    }
+
+    pub fn spin(iterations: u64) -> u64 {
+        // black_box keeps the optimizer from folding the loop away
+        let mut acc: u64 = 0;
+        for i in 0..iterations {
+            acc = black_box(acc.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(i));
+        }
+        acc
+    }
+
+    fn write_values(storage: &mut dyn Storage, count: u32, value_size: u32) {
+        let value = vec![0xab; value_size as usize];
+        for i in 0..count {
+            storage.set(&burn_key(i), &value);
+        }
+    }
+
+    fn read_values(storage: &dyn Storage, count: u32) -> u64 {
+        (0..count)
+            .filter_map(|i| storage.get(&burn_key(i)))
+            .map(|value| value.len() as u64)
+            .sum()
+    }
+
+    fn fill_memory(bytes: u32) -> u8 {
+        // writing every byte makes sure the pages are actually touched
+        let mut buffer = vec![0u8; bytes as usize];
+        for (i, byte) in buffer.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        black_box(&buffer)
+            .iter()
+            .fold(0u8, |acc, byte| acc.wrapping_add(*byte))
+    }
+
+    pub fn burn_cpu(iterations: u64) -> Response {
+        Response::new()
+            .add_attribute("method", "burn_cpu")
+            .add_attribute("iterations", iterations.to_string())
+            .add_attribute("result", spin(iterations).to_string())
+    }
+
+    pub fn burn_storage_writes(deps: DepsMut, count: u32, value_size: u32) -> Response {
+        write_values(deps.storage, count, value_size);
+
+        Response::new()
+            .add_attribute("method", "burn_storage_writes")
+            .add_attribute("count", count.to_string())
+            .add_attribute("value_size", value_size.to_string())
+    }
+
+    pub fn burn_storage_reads(deps: Deps, count: u32) -> Response {
+        Response::new()
+            .add_attribute("method", "burn_storage_reads")
+            .add_attribute("count", count.to_string())
+            .add_attribute("bytes_read", read_values(deps.storage, count).to_string())
+    }
+
+    pub fn burn_memory(bytes: u32) -> Response {
+        Response::new()
+            .add_attribute("method", "burn_memory")
+            .add_attribute("bytes", bytes.to_string())
+            .add_attribute("checksum", fill_memory(bytes).to_string())
+    }
+
+    /// Runs every part of `work`, writes first so the reads find them.
+    pub fn perform(storage: &mut dyn Storage, work: &Work) {
+        write_values(storage, work.storage_writes, work.value_size);
+        read_values(storage, work.storage_reads);
+        spin(work.cpu_iterations);
+        fill_memory(work.memory_bytes);
+    }
+
+    pub fn waste_then_revert(deps: DepsMut, work: Work) -> Result<Response, ContractError> {
+        perform(deps.storage, &work);
+        Err(ContractError::Reverted {})
+    }
+
+    pub fn waste_in_submsg(
+        env: Env,
+        work: Work,
+        reply_on: ReplyOn,
+    ) -> Result<Response, ContractError> {
+        let msg = WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_json_binary(&ExecuteMsg::WasteThenRevert { work })?,
+            funds: vec![],
+        };
+
+        Ok(Response::new()
+            .add_submessage(SubMsg {
+                id: WASTE_REPLY_ID,
+                payload: Binary::default(),
+                msg: msg.into(),
+                gas_limit: None,
+                reply_on: reply_on.clone(),
+            })
+            .add_attribute("method", "waste_in_submsg")
+            .add_attribute("reply_on", format!("{reply_on:?}")))
+    }
+
+    pub fn self_query(
+        deps: Deps,
+        env: Env,
+        count: u32,
+        iterations: u64,
+    ) -> Result<Response, ContractError> {
+        let query = QueryMsg::ExpensiveQuery { iterations };
+        let mut result: u64 = 0;
+        for _ in 0..count {
+            let res: ExpensiveQueryResponse = deps
+                .querier
+                .query_wasm_smart(&env.contract.address, &query)?;
+            result = result.wrapping_add(res.result);
+        }
+
+        Ok(Response::new()
+            .add_attribute("method", "self_query")
+            .add_attribute("count", count.to_string())
+            .add_attribute("result", result.to_string()))
+    }
+
+    pub fn emit_events(events: u32, attributes_per_event: u32, value_len: u32) -> Response {
+        let value = "x".repeat(value_len as usize);
+        let events = (0..events).map(|i| {
+            Event::new("flood")
+                .add_attribute("index", i.to_string())
+                .add_attributes((0..attributes_per_event).map(|j| (format!("attr_{j}"), &value)))
+        });
+
+        Response::new()
+            .add_events(events)
+            .add_attribute("method", "emit_events")
+    }
+
+    pub fn large_data(bytes: u32) -> Response {
+        Response::new()
+            .set_data(vec![0xab; bytes as usize])
+            .add_attribute("method", "large_data")
+            .add_attribute("bytes", bytes.to_string())
+    }
+
+    pub fn fill_storage(
+        deps: DepsMut,
+        keys: u32,
+        value_size: u32,
+    ) -> Result<Response, ContractError> {
+        let stored = FILLED_KEYS.may_load(deps.storage)?.unwrap_or_default();
+        let total = stored.checked_add(keys).ok_or(ContractError::Overflow {})?;
+        let value = vec![0xab; value_size as usize];
+        for i in stored..total {
+            deps.storage.set(&fill_key(i), &value);
+        }
+        FILLED_KEYS.save(deps.storage, &total)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "fill_storage")
+            .add_attribute("added", keys.to_string())
+            .add_attribute("keys", total.to_string()))
+    }
+
+    pub fn clear_storage(deps: DepsMut, limit: u32) -> Result<Response, ContractError> {
+        let stored = FILLED_KEYS.may_load(deps.storage)?.unwrap_or_default();
+        // removing from the end keeps the remaining keys numbered without gaps
+        let remaining = stored.saturating_sub(limit);
+        for i in (remaining..stored).rev() {
+            deps.storage.remove(&fill_key(i));
+        }
+        FILLED_KEYS.save(deps.storage, &remaining)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "clear_storage")
+            .add_attribute("removed", (stored - remaining).to_string())
+            .add_attribute("keys", remaining.to_string()))
+    }
+
+    pub fn secp256k1_verify(
+        deps: Deps,
+        count: u32,
+        message_hash: Binary,
+        signature: Binary,
+        public_key: Binary,
+    ) -> Result<Response, ContractError> {
+        let mut valid = true;
+        for _ in 0..count {
+            valid &= deps
+                .api
+                .secp256k1_verify(&message_hash, &signature, &public_key)?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("method", "secp256k1_verify")
+            .add_attribute("count", count.to_string())
+            .add_attribute("valid", valid.to_string()))
+    }
+
+    pub fn secp256k1_recover_pubkey(
+        deps: Deps,
+        count: u32,
+        message_hash: Binary,
+        signature: Binary,
+        recovery_param: u8,
+    ) -> Result<Response, ContractError> {
+        let mut public_key = vec![];
+        for _ in 0..count {
+            public_key =
+                deps.api
+                    .secp256k1_recover_pubkey(&message_hash, &signature, recovery_param)?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("method", "secp256k1_recover_pubkey")
+            .add_attribute("count", count.to_string())
+            .add_attribute("public_key", HexBinary::from(public_key).to_hex()))
+    }
+
+    pub fn ed25519_batch_verify(
+        deps: Deps,
+        count: u32,
+        messages: Vec<Binary>,
+        signatures: Vec<Binary>,
+        public_keys: Vec<Binary>,
+    ) -> Result<Response, ContractError> {
+        let messages: Vec<&[u8]> = messages.iter().map(Binary::as_slice).collect();
+        let signatures: Vec<&[u8]> = signatures.iter().map(Binary::as_slice).collect();
+        let public_keys: Vec<&[u8]> = public_keys.iter().map(Binary::as_slice).collect();
+        let mut valid = true;
+        for _ in 0..count {
+            valid &= deps
+                .api
+                .ed25519_batch_verify(&messages, &signatures, &public_keys)?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("method", "ed25519_batch_verify")
+            .add_attribute("count", count.to_string())
+            .add_attribute("signatures", signatures.len().to_string())
+            .add_attribute("valid", valid.to_string()))
+    }
+
+    pub fn sha256(iterations: u64, input_size: u32) -> Response {
+        let mut buffer = vec![0xab; input_size as usize];
+        let mut digest = [0u8; 32];
+        for _ in 0..iterations {
+            digest = Sha256::digest(&buffer).into();
+            let len = buffer.len().min(digest.len());
+            buffer[..len].copy_from_slice(&digest[..len]);
+        }
+
+        Response::new()
+            .add_attribute("method", "sha256")
+            .add_attribute("iterations", iterations.to_string())
+            .add_attribute("digest", HexBinary::from(digest).to_hex())
+    }
+
+    /// Submessage errors reach `reply` redacted down to their ABCI code, so
+    /// running out of gas shows up as the SDK's `ErrOutOfGas`.
+    pub fn is_out_of_gas(error: &str) -> bool {
+        error == "codespace: sdk, code: 11" || error.contains("out of gas")
+    }
+
+    pub fn call_with_gas_limit(
+        deps: DepsMut,
+        info: MessageInfo,
+        target: String,
+        msg: Binary,
+        gas_limit: u64,
+    ) -> Result<Response, ContractError> {
+        let target = deps.api.addr_validate(&target)?;
+        // the reply only runs on failure, so the call counts as completed
+        // until it says otherwise
+        GAS_LIMITED_CALL.save(
+            deps.storage,
+            &GasLimitedCall {
+                target: target.to_string(),
+                gas_limit,
+                error: None,
+                out_of_gas: false,
+            },
+        )?;
+
+        let msg = WasmMsg::Execute {
+            contract_addr: target.to_string(),
+            msg,
+            funds: info.funds,
+        };
+
+        Ok(Response::new()
+            .add_submessage(
+                SubMsg::reply_on_error(msg, GAS_LIMIT_REPLY_ID).with_gas_limit(gas_limit),
+            )
+            .add_attribute("method", "call_with_gas_limit")
+            .add_attribute("target", target)
+            .add_attribute("gas_limit", gas_limit.to_string()))
+    }
+
+    pub fn record_gas_limited_failure(
+        deps: DepsMut,
+        msg: Reply,
+    ) -> Result<Response, ContractError> {
+        let SubMsgResult::Err(error) = msg.result else {
+            return Err(StdError::generic_err("gas limited call replied on success").into());
+        };
+        let mut call = GAS_LIMITED_CALL.load(deps.storage)?;
+        call.out_of_gas = is_out_of_gas(&error);
+        call.error = Some(error);
+        GAS_LIMITED_CALL.save(deps.storage, &call)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "record_gas_limited_failure")
+            .add_attribute("out_of_gas", call.out_of_gas.to_string()))
+    }
+
+    pub fn record_reply(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+        let error = match msg.result {
+            SubMsgResult::Ok(_) => None,
+            SubMsgResult::Err(err) => Some(err),
+        };
+        let record = ReplyRecord {
+            id: msg.id,
+            gas_used: msg.gas_used,
+            error,
+        };
+        LAST_REPLY.save(deps.storage, &record)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "reply")
+            .add_attribute("id", record.id.to_string())
+            .add_attribute("failed", record.error.is_some().to_string()))
+    }
+}
+
+pub mod query {
+    use crate::msg::{
+        ExpensiveQueryResponse, GasLimitedCallResponse, LargeResponseResponse, LastReplyResponse,
+        StorageStatsResponse,
+    };
+    use crate::state::{FILLED_KEYS, GAS_LIMITED_CALL, LAST_REPLY};
+
+    use super::*;
+
+    pub fn query_last_reply(deps: Deps) -> StdResult<LastReplyResponse> {
+        Ok(LastReplyResponse {
+            reply: LAST_REPLY.may_load(deps.storage)?,
+        })
+    }
+
+    pub fn query_gas_limited_call(deps: Deps) -> StdResult<GasLimitedCallResponse> {
+        Ok(GasLimitedCallResponse {
+            call: GAS_LIMITED_CALL.may_load(deps.storage)?,
+        })
+    }
+
+    pub fn query_storage_stats(deps: Deps) -> StdResult<StorageStatsResponse> {
+        Ok(StorageStatsResponse {
+            keys: FILLED_KEYS.may_load(deps.storage)?.unwrap_or_default(),
+        })
+    }
+
+    pub fn query_expensive(iterations: u64) -> ExpensiveQueryResponse {
+        ExpensiveQueryResponse {
+            result: execute::spin(iterations),
+        }
+    }
+
+    pub fn query_large_response(bytes: u32) -> LargeResponseResponse {
+        LargeResponseResponse {
+            payload: "x".repeat(bytes as usize),
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{RecoverPubkeyError, StdError, VerificationError};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,10 +6,22 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Verification(#[from] VerificationError),
+
+    #[error("{0}")]
+    RecoverPubkey(#[from] RecoverPubkeyError),
+
     #[error("Unauthorized")]
     Unauthorized {},
     // Add any other custom errors you like here.
     // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
     #[error("Overflow")]
     Overflow {},
+
+    #[error("Reverted after burning gas")]
+    Reverted {},
+
+    #[error("Unknown reply id {id}")]
+    UnknownReplyId { id: u64 },
 }
@@ -0,0 +1,31 @@
+//! A static blob that brings the optimized wasm close to wasmd's default
+//! `MaxWasmCodeSize` of 800 KiB, for testing store-code limits.
+
+use std::hint::black_box;
+
+/// Leaves roughly 100 KiB for the contract's own code.
+pub const BLOB_SIZE: usize = 700 * 1024;
+
+/// Pseudo-random so that gzip compressed uploads stay large too.
+static BLOB: [u8; BLOB_SIZE] = blob();
+
+const fn blob() -> [u8; BLOB_SIZE] {
+    let mut blob = [0u8; BLOB_SIZE];
+    // xorshift64, a loop because iterators are not available in const fns
+    let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+    let mut i = 0;
+    while i < BLOB_SIZE {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        blob[i] = state as u8;
+        i += 1;
+    }
+    blob
+}
+
+/// Reads a byte the compiler cannot predict, so the blob is kept in the
+/// data section instead of being dropped as unused.
+pub fn last_byte() -> u8 {
+    BLOB[black_box(BLOB_SIZE - 1)]
+}
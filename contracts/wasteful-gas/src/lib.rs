@@ -1,5 +1,13 @@
 pub mod contract;
 mod error;
+#[cfg(feature = "large-code")]
+pub mod large_code;
 pub mod msg;
+pub mod state;
+
+#[cfg(test)]
+mod multitest;
+#[cfg(test)]
+pub mod testing;
 
 pub use crate::error::ContractError;
@@ -1,14 +1,181 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Binary, ReplyOn};
 
+use crate::state::{GasLimitedCall, ReplyRecord};
+
+#[cw_serde]
+#[derive(Default)]
+pub struct InstantiateMsg {
+    /// Gas to burn while instantiating, storage writes included.
+    #[serde(default)]
+    pub work: Option<Work>,
+}
+
+/// Gas burned in a single call, combining the burning modes. Parts left out
+/// do nothing.
 #[cw_serde]
-pub struct InstantiateMsg {}
+#[derive(Default)]
+pub struct Work {
+    #[serde(default)]
+    pub cpu_iterations: u64,
+    #[serde(default)]
+    pub storage_writes: u32,
+    #[serde(default)]
+    pub value_size: u32,
+    #[serde(default)]
+    pub storage_reads: u32,
+    #[serde(default)]
+    pub memory_bytes: u32,
+}
 
 #[cw_serde]
 pub enum ExecuteMsg {
     WasteGas {},
     NoGas {},
+    /// Runs a tight arithmetic loop `iterations` times.
+    BurnCpu {
+        iterations: u64,
+        #[serde(default)]
+        revert: bool,
+    },
+    /// Writes `count` keys holding `value_size` bytes each. The same keys are
+    /// overwritten on every call, so storage does not grow across calls.
+    BurnStorageWrites {
+        count: u32,
+        value_size: u32,
+        #[serde(default)]
+        revert: bool,
+    },
+    /// Reads the first `count` keys written by `BurnStorageWrites`. Keys that
+    /// were never written still pay the flat cost of a read.
+    BurnStorageReads {
+        count: u32,
+        #[serde(default)]
+        revert: bool,
+    },
+    /// Allocates and fills a buffer of `bytes` bytes.
+    BurnMemory {
+        bytes: u32,
+        #[serde(default)]
+        revert: bool,
+    },
+    /// Performs `work`, storage writes included, and then fails.
+    WasteThenRevert {
+        work: Work,
+    },
+    /// Dispatches `WasteThenRevert` to the contract itself as a submessage.
+    /// With `reply_on` set to `error` or `always` the failure is caught in
+    /// `reply` and this call succeeds.
+    WasteInSubmsg {
+        work: Work,
+        reply_on: ReplyOn,
+    },
+    /// Runs `ExpensiveQuery` against the contract itself `count` times.
+    SelfQuery {
+        count: u32,
+        iterations: u64,
+    },
+    /// Emits `events` events carrying `attributes_per_event` attributes whose
+    /// values are `value_len` bytes long.
+    EmitEvents {
+        events: u32,
+        attributes_per_event: u32,
+        value_len: u32,
+    },
+    /// Sets `bytes` bytes of response data.
+    LargeData {
+        bytes: u32,
+    },
+    /// Executes `msg` on `target` as a submessage limited to `gas_limit` gas,
+    /// forwarding the attached funds. The outcome is recorded in state.
+    CallWithGasLimit {
+        target: String,
+        msg: Binary,
+        gas_limit: u64,
+    },
+    /// Stores `keys` new keys holding `value_size` bytes each. Unlike
+    /// `BurnStorageWrites`, every call grows the contract's storage.
+    FillStorage {
+        keys: u32,
+        value_size: u32,
+    },
+    /// Removes up to `limit` of the keys stored by `FillStorage`, newest first.
+    ClearStorage {
+        limit: u32,
+    },
+    /// Verifies `signature` over `message_hash` with `public_key` `count`
+    /// times. Invalid signatures cost the same and are reported as such.
+    Secp256k1Verify {
+        count: u32,
+        message_hash: Binary,
+        signature: Binary,
+        public_key: Binary,
+    },
+    /// Recovers the public key that signed `message_hash` `count` times.
+    Secp256k1RecoverPubkey {
+        count: u32,
+        message_hash: Binary,
+        signature: Binary,
+        recovery_param: u8,
+    },
+    /// Verifies the whole batch `count` times. A single public key or message
+    /// is used for every signature, see `Api::ed25519_batch_verify`.
+    Ed25519BatchVerify {
+        count: u32,
+        messages: Vec<Binary>,
+        signatures: Vec<Binary>,
+        public_keys: Vec<Binary>,
+    },
+    /// Hashes an `input_size` byte buffer `iterations` times, feeding each
+    /// digest back into the buffer. There is no SHA-256 host function, so
+    /// this runs inside the contract.
+    Sha256 {
+        iterations: u64,
+        input_size: u32,
+    },
 }
 
 #[cw_serde]
 #[derive(QueryResponses)]
-pub enum QueryMsg {}
+pub enum QueryMsg {
+    /// The last submessage result handled by `reply`.
+    #[returns(LastReplyResponse)]
+    LastReply {},
+    /// Runs the `BurnCpu` loop inside a query.
+    #[returns(ExpensiveQueryResponse)]
+    ExpensiveQuery { iterations: u64 },
+    /// Returns a payload of exactly `bytes` bytes.
+    #[returns(LargeResponseResponse)]
+    LargeResponse { bytes: u32 },
+    /// The last `CallWithGasLimit` and its outcome.
+    #[returns(GasLimitedCallResponse)]
+    GasLimitedCall {},
+    #[returns(StorageStatsResponse)]
+    StorageStats {},
+}
+
+#[cw_serde]
+pub struct LastReplyResponse {
+    pub reply: Option<ReplyRecord>,
+}
+
+#[cw_serde]
+pub struct ExpensiveQueryResponse {
+    pub result: u64,
+}
+
+#[cw_serde]
+pub struct LargeResponseResponse {
+    pub payload: String,
+}
+
+#[cw_serde]
+pub struct GasLimitedCallResponse {
+    pub call: Option<GasLimitedCall>,
+}
+
+#[cw_serde]
+pub struct StorageStatsResponse {
+    /// Keys currently stored by `FillStorage`.
+    pub keys: u32,
+}
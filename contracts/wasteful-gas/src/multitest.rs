@@ -0,0 +1,138 @@
+use cosmwasm_std::testing::MockApi;
+use cosmwasm_std::{Addr, ReplyOn};
+use cw_multi_test::{App, ContractWrapper, Executor};
+
+use crate::contract::{execute, instantiate, query, reply, WASTE_REPLY_ID};
+use crate::msg::{
+    ExecuteMsg, ExpensiveQueryResponse, InstantiateMsg, LastReplyResponse, QueryMsg, Work,
+};
+use crate::state::burn_key;
+use crate::ContractError;
+
+fn setup() -> (App, Addr, Addr) {
+    let mut app = App::default();
+    let sender = MockApi::default().addr_make("sender");
+    let code_id = app.store_code(Box::new(
+        ContractWrapper::new(execute, instantiate, query).with_reply(reply),
+    ));
+    let contract = app
+        .instantiate_contract(
+            code_id,
+            sender.clone(),
+            &InstantiateMsg::default(),
+            &[],
+            "wasteful-gas",
+            None,
+        )
+        .unwrap();
+    (app, sender, contract)
+}
+
+fn work() -> Work {
+    Work {
+        cpu_iterations: 1_000,
+        storage_writes: 3,
+        value_size: 32,
+        ..Work::default()
+    }
+}
+
+fn last_reply(app: &App, contract: &Addr) -> LastReplyResponse {
+    app.wrap()
+        .query_wasm_smart(contract, &QueryMsg::LastReply {})
+        .unwrap()
+}
+
+#[test]
+fn test_waste_then_revert_rolls_back_writes() {
+    let (mut app, sender, contract) = setup();
+
+    let err = app
+        .execute_contract(
+            sender,
+            contract.clone(),
+            &ExecuteMsg::WasteThenRevert { work: work() },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        ContractError::Reverted {}.to_string()
+    );
+    assert_eq!(app.contract_storage(&contract).get(&burn_key(0)), None);
+}
+
+#[test]
+fn test_caught_submsg_failure_keeps_parent() {
+    let (mut app, sender, contract) = setup();
+
+    for reply_on in [ReplyOn::Error, ReplyOn::Always] {
+        app.execute_contract(
+            sender.clone(),
+            contract.clone(),
+            &ExecuteMsg::WasteInSubmsg {
+                work: work(),
+                reply_on,
+            },
+            &[],
+        )
+        .unwrap();
+
+        // only the child's writes are rolled back, the reply's are kept
+        assert_eq!(app.contract_storage(&contract).get(&burn_key(0)), None);
+        let reply = last_reply(&app, &contract).reply.unwrap();
+        assert_eq!(reply.id, WASTE_REPLY_ID);
+        assert!(reply.error.is_some());
+    }
+}
+
+#[test]
+fn test_uncaught_submsg_failure_fails_parent() {
+    let (mut app, sender, contract) = setup();
+
+    for reply_on in [ReplyOn::Success, ReplyOn::Never] {
+        let err = app
+            .execute_contract(
+                sender.clone(),
+                contract.clone(),
+                &ExecuteMsg::WasteInSubmsg {
+                    work: work(),
+                    reply_on,
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.root_cause().to_string(),
+            ContractError::Reverted {}.to_string()
+        );
+        assert_eq!(last_reply(&app, &contract).reply, None);
+    }
+}
+
+#[test]
+fn test_self_query() {
+    let (mut app, sender, contract) = setup();
+    let expected: ExpensiveQueryResponse = app
+        .wrap()
+        .query_wasm_smart(&contract, &QueryMsg::ExpensiveQuery { iterations: 100 })
+        .unwrap();
+
+    let res = app
+        .execute_contract(
+            sender,
+            contract.clone(),
+            &ExecuteMsg::SelfQuery {
+                count: 3,
+                iterations: 100,
+            },
+            &[],
+        )
+        .unwrap();
+    let result = expected.result.wrapping_mul(3).to_string();
+    assert!(res
+        .events
+        .iter()
+        .flat_map(|e| &e.attributes)
+        .any(|a| a.key == "result" && a.value == result));
+}
@@ -0,0 +1,42 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cw_storage_plus::Item;
+
+/// Prefixes of the raw keys written by the storage modes. Values are stored
+/// without serialization so a write costs exactly its size in bytes.
+const BURN_PREFIX: &[u8] = b"burn";
+const FILL_PREFIX: &[u8] = b"fill";
+
+pub fn burn_key(index: u32) -> Vec<u8> {
+    [BURN_PREFIX, &index.to_be_bytes()].concat()
+}
+
+/// Keys written by `FillStorage`, numbered from zero without gaps.
+pub fn fill_key(index: u32) -> Vec<u8> {
+    [FILL_PREFIX, &index.to_be_bytes()].concat()
+}
+
+/// Number of keys currently stored by `FillStorage`.
+pub const FILLED_KEYS: Item<u32> = Item::new("filled_keys");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ReplyRecord {
+    pub id: u64,
+    pub gas_used: u64,
+    /// The error of a failed submessage, `None` if it succeeded.
+    pub error: Option<String>,
+}
+
+pub const LAST_REPLY: Item<ReplyRecord> = Item::new("last_reply");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct GasLimitedCall {
+    pub target: String,
+    pub gas_limit: u64,
+    /// Set once the reply reports that the call failed.
+    pub error: Option<String>,
+    pub out_of_gas: bool,
+}
+
+pub const GAS_LIMITED_CALL: Item<GasLimitedCall> = Item::new("gas_limited_call");
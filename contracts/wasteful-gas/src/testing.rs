@@ -0,0 +1,518 @@
+use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env, MockApi};
+use cosmwasm_std::{
+    from_json, to_json_binary, Binary, Deps, DepsMut, HexBinary, Reply, ReplyOn, Response, Storage,
+    SubMsg, SubMsgResult, WasmMsg,
+};
+
+use crate::contract::{execute, instantiate, query, reply, GAS_LIMIT_REPLY_ID, WASTE_REPLY_ID};
+use crate::msg::{
+    ExecuteMsg, ExpensiveQueryResponse, GasLimitedCallResponse, InstantiateMsg,
+    LargeResponseResponse, LastReplyResponse, QueryMsg, StorageStatsResponse, Work,
+};
+use crate::state::{burn_key, fill_key, GasLimitedCall, ReplyRecord};
+use crate::ContractError;
+
+fn run(deps: DepsMut, msg: ExecuteMsg) -> Result<Response, ContractError> {
+    let sender = MockApi::default().addr_make("sender");
+    execute(deps, mock_env(), message_info(&sender, &[]), msg)
+}
+
+fn attribute(res: &Response, key: &str) -> String {
+    res.attributes
+        .iter()
+        .find(|a| a.key == key)
+        .map(|a| a.value.clone())
+        .unwrap()
+}
+
+fn hex(value: &str) -> Binary {
+    HexBinary::from_hex(value).unwrap().to_vec().into()
+}
+
+fn gas_limited_call(deps: Deps) -> Option<GasLimitedCall> {
+    let res: GasLimitedCallResponse =
+        from_json(query(deps, mock_env(), QueryMsg::GasLimitedCall {}).unwrap()).unwrap();
+    res.call
+}
+
+fn last_reply(deps: Deps) -> LastReplyResponse {
+    from_json(query(deps, mock_env(), QueryMsg::LastReply {}).unwrap()).unwrap()
+}
+
+#[test]
+fn test_burn_cpu() {
+    let mut deps = mock_dependencies();
+    let owner = MockApi::default().addr_make("owner");
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&owner, &[]),
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+
+    let res = run(
+        deps.as_mut(),
+        ExecuteMsg::BurnCpu {
+            iterations: 1_000,
+            revert: false,
+        },
+    )
+    .unwrap();
+    assert_eq!(attribute(&res, "iterations"), "1000");
+
+    let err = run(
+        deps.as_mut(),
+        ExecuteMsg::BurnCpu {
+            iterations: 1_000,
+            revert: true,
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Reverted {}));
+}
+
+#[test]
+fn test_burn_storage() {
+    let mut deps = mock_dependencies();
+
+    run(
+        deps.as_mut(),
+        ExecuteMsg::BurnStorageWrites {
+            count: 3,
+            value_size: 100,
+            revert: false,
+        },
+    )
+    .unwrap();
+    assert_eq!(deps.storage.get(&burn_key(2)), Some(vec![0xab; 100]));
+    assert_eq!(deps.storage.get(&burn_key(3)), None);
+
+    // the fourth key was never written and adds nothing
+    let res = run(
+        deps.as_mut(),
+        ExecuteMsg::BurnStorageReads {
+            count: 4,
+            revert: false,
+        },
+    )
+    .unwrap();
+    assert_eq!(attribute(&res, "bytes_read"), "300");
+}
+
+#[test]
+fn test_burn_memory() {
+    let mut deps = mock_dependencies();
+
+    let res = run(
+        deps.as_mut(),
+        ExecuteMsg::BurnMemory {
+            bytes: 512,
+            revert: false,
+        },
+    )
+    .unwrap();
+    // 0 + 1 + ... + 255, twice, wrapped to a byte
+    assert_eq!(attribute(&res, "checksum"), "0");
+}
+
+#[test]
+fn test_revert_defaults_to_false() {
+    let msg: ExecuteMsg = cosmwasm_std::from_json(r#"{"burn_cpu":{"iterations":1}}"#).unwrap();
+    assert_eq!(
+        msg,
+        ExecuteMsg::BurnCpu {
+            iterations: 1,
+            revert: false,
+        }
+    );
+}
+
+#[test]
+fn test_waste_then_revert() {
+    let mut deps = mock_dependencies();
+
+    let err = run(
+        deps.as_mut(),
+        ExecuteMsg::WasteThenRevert {
+            work: Work {
+                cpu_iterations: 100,
+                storage_writes: 2,
+                value_size: 10,
+                ..Work::default()
+            },
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Reverted {}));
+    // the mock storage has no transactions, so the writes that the chain
+    // would roll back are still visible here
+    assert_eq!(deps.storage.get(&burn_key(1)), Some(vec![0xab; 10]));
+}
+
+#[test]
+fn test_waste_in_submsg() {
+    let mut deps = mock_dependencies();
+    let work = Work {
+        storage_writes: 1,
+        value_size: 10,
+        ..Work::default()
+    };
+
+    let res = run(
+        deps.as_mut(),
+        ExecuteMsg::WasteInSubmsg {
+            work: work.clone(),
+            reply_on: ReplyOn::Error,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg {
+            id: WASTE_REPLY_ID,
+            payload: Binary::default(),
+            msg: WasmMsg::Execute {
+                contract_addr: mock_env().contract.address.to_string(),
+                msg: to_json_binary(&ExecuteMsg::WasteThenRevert { work }).unwrap(),
+                funds: vec![],
+            }
+            .into(),
+            gas_limit: None,
+            reply_on: ReplyOn::Error,
+        }]
+    );
+
+    assert_eq!(last_reply(deps.as_ref()).reply, None);
+
+    reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: WASTE_REPLY_ID,
+            payload: Binary::default(),
+            gas_used: 1_234,
+            result: SubMsgResult::Err("Reverted after burning gas".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        last_reply(deps.as_ref()).reply,
+        Some(ReplyRecord {
+            id: WASTE_REPLY_ID,
+            gas_used: 1_234,
+            error: Some("Reverted after burning gas".to_string()),
+        })
+    );
+
+    let err = reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: 99,
+            payload: Binary::default(),
+            gas_used: 0,
+            result: SubMsgResult::Err("boom".to_string()),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::UnknownReplyId { id: 99 }));
+}
+
+#[test]
+fn test_gas_heavy_queries() {
+    let deps = mock_dependencies();
+
+    let res: ExpensiveQueryResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ExpensiveQuery { iterations: 0 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.result, 0);
+
+    let res: LargeResponseResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::LargeResponse { bytes: 1_000 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.payload.len(), 1_000);
+}
+
+#[test]
+fn test_flooding() {
+    let mut deps = mock_dependencies();
+
+    let res = run(
+        deps.as_mut(),
+        ExecuteMsg::EmitEvents {
+            events: 3,
+            attributes_per_event: 4,
+            value_len: 16,
+        },
+    )
+    .unwrap();
+    assert_eq!(res.events.len(), 3);
+    assert_eq!(res.events[2].ty, "flood");
+    // the index attribute comes first
+    assert_eq!(res.events[2].attributes.len(), 5);
+    assert_eq!(res.events[2].attributes[4].key, "attr_3");
+    assert_eq!(res.events[2].attributes[4].value, "x".repeat(16));
+
+    let res = run(deps.as_mut(), ExecuteMsg::LargeData { bytes: 2_048 }).unwrap();
+    assert_eq!(res.data, Some(Binary::from(vec![0xab; 2_048])));
+}
+
+#[test]
+fn test_call_with_gas_limit() {
+    let mut deps = mock_dependencies();
+    let target = deps.api.addr_make("target");
+    let child = to_json_binary(&ExecuteMsg::BurnCpu {
+        iterations: 1_000_000,
+        revert: false,
+    })
+    .unwrap();
+
+    let res = run(
+        deps.as_mut(),
+        ExecuteMsg::CallWithGasLimit {
+            target: target.to_string(),
+            msg: child.clone(),
+            gas_limit: 50_000,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_on_error(
+            WasmMsg::Execute {
+                contract_addr: target.to_string(),
+                msg: child,
+                funds: vec![],
+            },
+            GAS_LIMIT_REPLY_ID,
+        )
+        .with_gas_limit(50_000)]
+    );
+    // without a reply the call completed
+    assert_eq!(
+        gas_limited_call(deps.as_ref()),
+        Some(GasLimitedCall {
+            target: target.to_string(),
+            gas_limit: 50_000,
+            error: None,
+            out_of_gas: false,
+        })
+    );
+
+    let mut fail = |error: &str| {
+        reply(
+            deps.as_mut(),
+            mock_env(),
+            Reply {
+                id: GAS_LIMIT_REPLY_ID,
+                payload: Binary::default(),
+                gas_used: 50_000,
+                result: SubMsgResult::Err(error.to_string()),
+            },
+        )
+        .unwrap();
+        gas_limited_call(deps.as_ref()).unwrap()
+    };
+    let call = fail("codespace: sdk, code: 11");
+    assert!(call.out_of_gas);
+    assert_eq!(call.error.as_deref(), Some("codespace: sdk, code: 11"));
+    assert!(!fail("codespace: wasm, code: 5").out_of_gas);
+}
+
+#[test]
+fn test_fill_and_clear_storage() {
+    let mut deps = mock_dependencies();
+    let keys = |deps: Deps| -> u32 {
+        let res: StorageStatsResponse =
+            from_json(query(deps, mock_env(), QueryMsg::StorageStats {}).unwrap()).unwrap();
+        res.keys
+    };
+    assert_eq!(keys(deps.as_ref()), 0);
+
+    // every fill adds new keys instead of overwriting
+    for _ in 0..2 {
+        run(
+            deps.as_mut(),
+            ExecuteMsg::FillStorage {
+                keys: 5,
+                value_size: 64,
+            },
+        )
+        .unwrap();
+    }
+    assert_eq!(keys(deps.as_ref()), 10);
+    assert_eq!(deps.storage.get(&fill_key(9)), Some(vec![0xab; 64]));
+
+    let res = run(deps.as_mut(), ExecuteMsg::ClearStorage { limit: 4 }).unwrap();
+    assert_eq!(attribute(&res, "removed"), "4");
+    assert_eq!(keys(deps.as_ref()), 6);
+    assert_eq!(deps.storage.get(&fill_key(6)), None);
+    assert!(deps.storage.get(&fill_key(5)).is_some());
+
+    let res = run(deps.as_mut(), ExecuteMsg::ClearStorage { limit: 100 }).unwrap();
+    assert_eq!(attribute(&res, "removed"), "6");
+    assert_eq!(keys(deps.as_ref()), 0);
+    assert_eq!(deps.storage.get(&fill_key(0)), None);
+}
+
+// signed over the SHA-256 of "wasteful-gas" with the secret key [7; 32]
+const MESSAGE_HASH: &str = "af90d77a5c4957d5f5519c51aa669c6afd8d58f7f7eba6e2b5efe7107b21756b";
+const SECP256K1_SIGNATURE: &str = "0886ce699c4ee7a6f9ced0ab634a1509fe9410969de4e2819ce14df135714e5c\
+                                   624271d65a6af2b9dc938734d8a40fae5557577c846bd56ffc8570a1b23dac30";
+const SECP256K1_PUBKEY: &str = "02989c0b76cb563971fdc9bef31ec06c3560f3249d6ee9e5d83c57625596e05f6f";
+const SECP256K1_UNCOMPRESSED: &str = "04989c0b76cb563971fdc9bef31ec06c3560f3249d6ee9e5d83c57625596e05f6f\
+                                      631f4d05b3ae518776ee08755a7703e64b2ebc32547504de0b55a142d4ecdf80";
+// signed over "wasteful-gas" with the secret key [9; 32]
+const ED25519_SIGNATURE: &str = "5af65b76cab4fe609827d840b2b74bd758cac55176ba66a087aad2ac824c8c84\
+                                 a7182e5477d1f6774c100e1ffb917d1d77497c2b1e6d0859964b565f43c55d02";
+const ED25519_PUBKEY: &str = "fd1724385aa0c75b64fb78cd602fa1d991fdebf76b13c58ed702eac835e9f618";
+
+#[test]
+fn test_secp256k1() {
+    let mut deps = mock_dependencies();
+
+    let mut verify = |message_hash: Binary| {
+        run(
+            deps.as_mut(),
+            ExecuteMsg::Secp256k1Verify {
+                count: 3,
+                message_hash,
+                signature: hex(SECP256K1_SIGNATURE),
+                public_key: hex(SECP256K1_PUBKEY),
+            },
+        )
+    };
+    assert_eq!(
+        attribute(&verify(hex(MESSAGE_HASH)).unwrap(), "valid"),
+        "true"
+    );
+    let mut other_hash = hex(MESSAGE_HASH).to_vec();
+    other_hash[0] ^= 1;
+    assert_eq!(
+        attribute(&verify(other_hash.into()).unwrap(), "valid"),
+        "false"
+    );
+    let err = verify(Binary::from(b"too short")).unwrap_err();
+    assert!(matches!(err, ContractError::Verification(_)));
+
+    let res = run(
+        deps.as_mut(),
+        ExecuteMsg::Secp256k1RecoverPubkey {
+            count: 2,
+            message_hash: hex(MESSAGE_HASH),
+            signature: hex(SECP256K1_SIGNATURE),
+            recovery_param: 0,
+        },
+    )
+    .unwrap();
+    assert_eq!(attribute(&res, "public_key"), SECP256K1_UNCOMPRESSED);
+}
+
+#[test]
+fn test_ed25519_batch_verify() {
+    let mut deps = mock_dependencies();
+
+    let res = run(
+        deps.as_mut(),
+        ExecuteMsg::Ed25519BatchVerify {
+            count: 2,
+            messages: vec![Binary::from(b"wasteful-gas"); 3],
+            signatures: vec![hex(ED25519_SIGNATURE); 3],
+            // a single key verifies every signature
+            public_keys: vec![hex(ED25519_PUBKEY)],
+        },
+    )
+    .unwrap();
+    assert_eq!(attribute(&res, "signatures"), "3");
+    assert_eq!(attribute(&res, "valid"), "true");
+
+    let res = run(
+        deps.as_mut(),
+        ExecuteMsg::Ed25519BatchVerify {
+            count: 1,
+            messages: vec![Binary::from(b"wasteful-gas"), Binary::from(b"other")],
+            signatures: vec![hex(ED25519_SIGNATURE); 2],
+            public_keys: vec![hex(ED25519_PUBKEY)],
+        },
+    )
+    .unwrap();
+    assert_eq!(attribute(&res, "valid"), "false");
+}
+
+#[test]
+fn test_sha256() {
+    let digest = |iterations: u64, input_size: u32| {
+        let mut deps = mock_dependencies();
+        let res = run(
+            deps.as_mut(),
+            ExecuteMsg::Sha256 {
+                iterations,
+                input_size,
+            },
+        )
+        .unwrap();
+        attribute(&res, "digest")
+    };
+
+    assert_eq!(digest(0, 64), "00".repeat(32));
+    // the digest of an empty buffer never changes
+    assert_eq!(
+        digest(5, 0),
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+    assert_ne!(digest(1, 64), digest(2, 64));
+}
+
+#[test]
+fn test_instantiate_work() {
+    let mut deps = mock_dependencies();
+    let owner = deps.api.addr_make("owner");
+
+    let msg: InstantiateMsg = from_json("{}").unwrap();
+    assert_eq!(msg, InstantiateMsg { work: None });
+
+    let res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&owner, &[]),
+        InstantiateMsg {
+            work: Some(Work {
+                storage_writes: 2,
+                value_size: 16,
+                ..Work::default()
+            }),
+        },
+    )
+    .unwrap();
+    assert_eq!(attribute(&res, "work"), "true");
+    assert_eq!(deps.storage.get(&burn_key(1)), Some(vec![0xab; 16]));
+}
+
+#[cfg(feature = "large-code")]
+#[test]
+fn test_large_code() {
+    let mut deps = mock_dependencies();
+    let owner = deps.api.addr_make("owner");
+
+    let res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&owner, &[]),
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+    assert_eq!(attribute(&res, "blob_size"), (700 * 1024).to_string());
+}
@@ -0,0 +1,626 @@
+use cosmwasm_std::{coin, coins, Addr, Binary, Coin, FullDelegation};
+use nibiru_std::proto::nibiru::oracle::{
+    ExchangeRateTuple, QueryExchangeRateRequest, QueryExchangeRateResponse,
+    QueryExchangeRatesRequest, QueryExchangeRatesResponse,
+};
+use nibiru_std::proto::NibiruStargateQuery;
+use prost::{Message, Name};
+
+/// Denom of the funds attached to payable cases.
+pub const DENOM: &str = "unibi";
+/// Validators of the mock staking module, the same for every contract. The
+/// staking contract starts out with the first two.
+pub const VALIDATORS: [&str; 3] = ["nibivaloper1alpha", "nibivaloper1beta", "nibivaloper1gamma"];
+/// Instantiates every contract and owns it.
+pub const CREATOR: &str = "creator";
+/// Sends the execute messages unless they say otherwise.
+pub const SENDER: &str = "sender";
+
+/// Messages are kept as JSON rather than the contracts' own types: depending on
+/// the contract crates with their `library` feature would unify it into the
+/// workspace's wasm builds and strip their entry points.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Call {
+    Execute {
+        sender: String,
+        msg: Binary,
+        funds: Vec<Coin>,
+    },
+    Query {
+        msg: Binary,
+    },
+    /// The chain's reply to a submessage, as the JSON of a `Reply`.
+    Reply {
+        msg: Binary,
+    },
+}
+
+impl Call {
+    pub fn execute(msg: impl Into<String>) -> Self {
+        Self::execute_with_funds(msg, vec![])
+    }
+
+    pub fn execute_with_funds(msg: impl Into<String>, funds: Vec<Coin>) -> Self {
+        Call::Execute {
+            sender: addr(SENDER),
+            msg: Binary::from(msg.into().into_bytes()),
+            funds,
+        }
+    }
+
+    pub fn query(msg: impl Into<String>) -> Self {
+        Call::Query {
+            msg: Binary::from(msg.into().into_bytes()),
+        }
+    }
+
+    pub fn reply(msg: impl Into<String>) -> Self {
+        Call::Reply {
+            msg: Binary::from(msg.into().into_bytes()),
+        }
+    }
+
+    /// Sends an execute message from `name` instead of the default sender.
+    pub fn sent_by(self, name: &str) -> Self {
+        match self {
+            Call::Execute { msg, funds, .. } => Call::Execute {
+                sender: addr(name),
+                msg,
+                funds,
+            },
+            call => call,
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Call::Execute { .. } => "execute",
+            Call::Query { .. } => "query",
+            Call::Reply { .. } => "reply",
+        }
+    }
+}
+
+/// A measured call, run on a freshly instantiated contract after `setup`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Case {
+    pub name: &'static str,
+    pub setup: Vec<Call>,
+    pub call: Call,
+}
+
+impl Case {
+    pub fn new(name: &'static str, call: Call) -> Self {
+        Case {
+            name,
+            setup: vec![],
+            call,
+        }
+    }
+
+    pub fn with_setup(mut self, setup: Vec<Call>) -> Self {
+        self.setup = setup;
+        self
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContractCases {
+    /// File stem of the compiled artifact, e.g. `wasteful_gas` for `wasteful_gas.wasm`.
+    pub contract: &'static str,
+    pub instantiate: Binary,
+    pub cases: Vec<Case>,
+    /// Balances of the mock bank module by address. Messages returned by the
+    /// contract are not executed, so these don't change during a case.
+    pub balances: Vec<(String, Vec<Coin>)>,
+    /// The contract's delegations in the mock staking module.
+    pub delegations: Vec<FullDelegation>,
+    /// Protobuf responses to gRPC queries by path, standing in for the
+    /// Nibiru modules the mock chain doesn't have.
+    pub grpc: Vec<(String, Binary)>,
+}
+
+impl ContractCases {
+    fn new(contract: &'static str, instantiate: impl Into<String>, cases: Vec<Case>) -> Self {
+        ContractCases {
+            contract,
+            instantiate: Binary::from(instantiate.into().into_bytes()),
+            cases,
+            balances: vec![],
+            delegations: vec![],
+            grpc: vec![],
+        }
+    }
+
+    fn with_balances(mut self, balances: Vec<(String, Vec<Coin>)>) -> Self {
+        self.balances = balances;
+        self
+    }
+
+    fn with_delegations(mut self, delegations: Vec<FullDelegation>) -> Self {
+        self.delegations = delegations;
+        self
+    }
+
+    fn with_grpc(mut self, grpc: Vec<(String, Binary)>) -> Self {
+        self.grpc = grpc;
+        self
+    }
+}
+
+/// Every contract in the workspace with the message variants worth measuring.
+pub fn all() -> Vec<ContractCases> {
+    vec![
+        counter(),
+        vault(),
+        bank_transfer(),
+        oracle_query(),
+        staking(),
+        wasteful_gas(),
+        infinite_loop(),
+    ]
+}
+
+/// `counter` and `vault` share their messages.
+fn counter_cases() -> Vec<Case> {
+    vec![
+        Case::new(
+            "increment_counter",
+            Call::execute(r#"{"increment_counter":{"by":1}}"#),
+        ),
+        Case::new("reset_counter", Call::execute(r#"{"reset_counter":{}}"#)),
+        Case::new("get_counter", Call::query(r#"{"get_counter":{}}"#)),
+    ]
+}
+
+pub fn counter() -> ContractCases {
+    ContractCases::new("counter", "{}", counter_cases())
+}
+
+pub fn vault() -> ContractCases {
+    ContractCases::new("vault", "{}", counter_cases())
+}
+
+pub fn bank_transfer() -> ContractCases {
+    let recipient = addr("recipient");
+    let spender = addr("spender");
+    let sender = addr(SENDER);
+    let transfer = format!(r#"{{"bank_transfer":{{"recipient":"{recipient}"}}}}"#);
+    let safe_transfer = Call::execute_with_funds(
+        format!(r#"{{"safe_transfer":{{"recipient":"{recipient}"}}}}"#),
+        coins(100, DENOM),
+    );
+    // the send of the first transfer failed, its id is the payload
+    let failed_send = Call::reply(
+        r#"{"id":1,"payload":"MQ==","gas_used":0,"result":{"error":"insufficient funds"}}"#,
+    );
+    let deposit = Call::execute_with_funds(r#"{"deposit":{}}"#, coins(100, DENOM));
+    let approve = Call::execute(format!(
+        r#"{{"approve":{{"spender":"{spender}","amount":{{"denom":"{DENOM}","amount":"50"}},"expires":null}}}}"#
+    ));
+    ContractCases::new(
+        "bank_transfer",
+        r#"{"fee":null}"#,
+        vec![
+            Case::new(
+                "bank_transfer",
+                Call::execute_with_funds(&transfer, coins(100, DENOM)),
+            ),
+            Case::new("safe_transfer", safe_transfer.clone()),
+            Case::new("refund_failed_transfer", failed_send.clone())
+                .with_setup(vec![safe_transfer.clone()]),
+            Case::new("claim_refund", Call::execute(r#"{"claim_refund":{}}"#))
+                .with_setup(vec![safe_transfer.clone(), failed_send.clone()]),
+            Case::new(
+                "update_fee",
+                Call::execute(format!(
+                    r#"{{"update_fee":{{"fee":{{"treasury":"{}","bps":50,"limits":[]}}}}}}"#,
+                    addr("treasury")
+                ))
+                .sent_by(CREATOR),
+            ),
+            Case::new(
+                "transfer_fun_token",
+                Call::execute_with_funds(
+                    r#"{"transfer_fun_token":{"to_eth_addr":"0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"}}"#,
+                    coins(100, DENOM),
+                ),
+            ),
+            Case::new("deposit", deposit.clone()),
+            Case::new(
+                "withdraw",
+                Call::execute(format!(
+                    r#"{{"withdraw":{{"amount":{{"denom":"{DENOM}","amount":"50"}}}}}}"#
+                )),
+            )
+            .with_setup(vec![deposit.clone()]),
+            Case::new("approve", approve.clone()).with_setup(vec![deposit.clone()]),
+            Case::new(
+                "transfer_from",
+                Call::execute(format!(
+                    r#"{{"transfer_from":{{"owner":"{sender}","recipient":"{recipient}","amount":{{"denom":"{DENOM}","amount":"50"}}}}}}"#
+                ))
+                .sent_by("spender"),
+            )
+            .with_setup(vec![deposit.clone(), approve.clone()]),
+            Case::new("config", Call::query(r#"{"config":{}}"#)),
+            Case::new(
+                "contract_balance",
+                Call::query(format!(r#"{{"contract_balance":{{"denom":"{DENOM}"}}}}"#)),
+            ),
+            Case::new("all_balances", Call::query(r#"{"all_balances":{}}"#)),
+            Case::new(
+                "transfer_history",
+                Call::query(format!(
+                    r#"{{"transfer_history":{{"address":"{recipient}"}}}}"#
+                )),
+            )
+            .with_setup(vec![Call::execute_with_funds(&transfer, coins(100, DENOM))]),
+            Case::new(
+                "refund",
+                Call::query(format!(r#"{{"refund":{{"address":"{sender}"}}}}"#)),
+            )
+            .with_setup(vec![safe_transfer, failed_send]),
+            Case::new(
+                "escrow",
+                Call::query(format!(r#"{{"escrow":{{"address":"{sender}"}}}}"#)),
+            )
+            .with_setup(vec![deposit.clone()]),
+            Case::new(
+                "allowance",
+                Call::query(format!(
+                    r#"{{"allowance":{{"owner":"{sender}","spender":"{spender}"}}}}"#
+                )),
+            )
+            .with_setup(vec![deposit, approve]),
+        ],
+    )
+}
+
+pub fn oracle_query() -> ContractCases {
+    let rate = |pair: &str, exchange_rate: &str| ExchangeRateTuple {
+        pair: pair.to_string(),
+        exchange_rate: exchange_rate.to_string(),
+    };
+    let btc = QueryExchangeRateResponse {
+        exchange_rate: "65000.000000000000000000".to_string(),
+    };
+    let rates = QueryExchangeRatesResponse {
+        exchange_rates: vec![
+            rate("ubtc:uusd", "65000.000000000000000000"),
+            rate("ueth:uusd", "3500.000000000000000000"),
+            rate("unibi:uusd", "0.025000000000000000"),
+        ],
+    };
+    let rate_path = QueryExchangeRateRequest::default().path();
+    let twap_path = format!(
+        "/{}.Query/ExchangeRateTwap",
+        QueryExchangeRateRequest::PACKAGE
+    );
+    ContractCases::new(
+        "oracle_query",
+        "{}",
+        vec![
+            Case::new(
+                "get_exchange_rate",
+                Call::query(r#"{"get_exchange_rate":{"pair":"ubtc:uusd"}}"#),
+            ),
+            Case::new(
+                "get_exchange_rate_twap",
+                Call::query(r#"{"get_exchange_rate_twap":{"pair":"ubtc:uusd"}}"#),
+            ),
+            Case::new(
+                "get_exchange_rates",
+                Call::query(r#"{"get_exchange_rates":{}}"#),
+            ),
+        ],
+    )
+    .with_grpc(vec![
+        (rate_path, Binary::from(btc.encode_to_vec())),
+        (twap_path, Binary::from(btc.encode_to_vec())),
+        (
+            QueryExchangeRatesRequest {}.path(),
+            Binary::from(rates.encode_to_vec()),
+        ),
+    ])
+}
+
+pub fn staking() -> ContractCases {
+    let contract = addr("cosmos2contract");
+    let lst_denom = format!("tf/{contract}/stnibi");
+    let validators = VALIDATORS[..2]
+        .iter()
+        .map(|v| format!(r#"{{"address":"{v}","weight":1}}"#))
+        .collect::<Vec<_>>()
+        .join(",");
+    let stake = Call::execute_with_funds(r#"{"delegate_weighted":{}}"#, coins(1_000, DENOM));
+    let signal = Call::execute(r#"{"signal_vote":{"proposal_id":1,"option":"yes"}}"#);
+    let unstake = Call::execute_with_funds(r#"{"unstake":{}}"#, coins(500, &lst_denom));
+    let compound = Call::execute(r#"{"compound":{}}"#);
+    // the contract's delegations don't follow its messages, these are what
+    // the stake in the setups would have become, along with some rewards
+    let delegations = VALIDATORS[..2]
+        .iter()
+        .map(|v| {
+            FullDelegation::create(
+                Addr::unchecked(&contract),
+                v.to_string(),
+                coin(500, DENOM),
+                coin(500, DENOM),
+                coins(10, DENOM),
+            )
+        })
+        .collect();
+    ContractCases::new(
+        "staking",
+        format!(
+            r#"{{"validators":[{validators}],"unbonding_period":1814400,"compound_bounty":"0.01","compound_interval":3600,"lst_subdenom":"stnibi"}}"#
+        ),
+        vec![
+            Case::new(
+                "run",
+                Call::execute_with_funds(r#"{"run":{}}"#, coins(1_000, DENOM)),
+            ),
+            Case::new(
+                "delegate",
+                Call::execute_with_funds(
+                    format!(r#"{{"delegate":{{"validator":"{}"}}}}"#, VALIDATORS[0]),
+                    coins(1_000, DENOM),
+                ),
+            ),
+            Case::new("delegate_weighted", stake.clone()),
+            Case::new("unstake", unstake.clone()).with_setup(vec![stake.clone()]),
+            Case::new("submit_batch", Call::execute(r#"{"submit_batch":{}}"#))
+                .with_setup(vec![stake.clone(), unstake.clone()]),
+            // every case runs at the same block time, so the batch never
+            // matures and this records the error
+            Case::new("claim", Call::execute(r#"{"claim":{}}"#))
+                .with_setup(vec![stake.clone(), unstake]),
+            Case::new("compound", compound.clone()).with_setup(vec![stake.clone()]),
+            // the rewards accrued by the compound are in the contract's balance
+            Case::new(
+                "restake_rewards",
+                Call::reply(
+                    r#"{"id":1,"payload":"","gas_used":0,"result":{"ok":{"events":[],"msg_responses":[]}}}"#,
+                ),
+            )
+            .with_setup(vec![stake.clone(), compound]),
+            Case::new(
+                "rebalance",
+                Call::execute(r#"{"rebalance":{}}"#).sent_by(CREATOR),
+            )
+            .with_setup(vec![stake.clone()]),
+            Case::new(
+                "undelegate",
+                Call::execute(format!(
+                    r#"{{"undelegate":{{"validator":"{}","amount":{{"denom":"{DENOM}","amount":"100"}}}}}}"#,
+                    VALIDATORS[0]
+                ))
+                .sent_by(CREATOR),
+            )
+            .with_setup(vec![stake.clone()]),
+            Case::new(
+                "redelegate",
+                Call::execute(format!(
+                    r#"{{"redelegate":{{"src":"{}","dst":"{}","amount":{{"denom":"{DENOM}","amount":"100"}}}}}}"#,
+                    VALIDATORS[0], VALIDATORS[1]
+                ))
+                .sent_by(CREATOR),
+            )
+            .with_setup(vec![stake.clone()]),
+            Case::new(
+                "claim_rewards",
+                Call::execute(r#"{"claim_rewards":{}}"#).sent_by(CREATOR),
+            )
+            .with_setup(vec![stake.clone()]),
+            Case::new(
+                "add_validator",
+                Call::execute(format!(
+                    r#"{{"add_validator":{{"validator":"{}","weight":1}}}}"#,
+                    VALIDATORS[2]
+                ))
+                .sent_by(CREATOR),
+            ),
+            Case::new(
+                "remove_validator",
+                Call::execute(format!(
+                    r#"{{"remove_validator":{{"validator":"{}"}}}}"#,
+                    VALIDATORS[1]
+                ))
+                .sent_by(CREATOR),
+            ),
+            Case::new(
+                "update_compound_config",
+                Call::execute(r#"{"update_compound_config":{"bounty":"0.02","interval":7200}}"#)
+                    .sent_by(CREATOR),
+            ),
+            Case::new(
+                "vote",
+                Call::execute(r#"{"vote":{"proposal_id":1,"option":"yes"}}"#).sent_by(CREATOR),
+            ),
+            Case::new(
+                "vote_weighted",
+                Call::execute(
+                    r#"{"vote_weighted":{"proposal_id":1,"options":[{"option":"yes","weight":"0.6"},{"option":"no","weight":"0.4"}]}}"#,
+                )
+                .sent_by(CREATOR),
+            ),
+            Case::new("signal_vote", signal.clone()).with_setup(vec![stake.clone()]),
+            Case::new(
+                "cast_signaled_vote",
+                Call::execute(r#"{"cast_signaled_vote":{"proposal_id":1}}"#).sent_by(CREATOR),
+            )
+            .with_setup(vec![stake.clone(), signal.clone()]),
+            Case::new(
+                "sync_delegations",
+                Call::execute(r#"{"sync_delegations":{}}"#),
+            )
+            .with_setup(vec![stake.clone()]),
+            Case::new("config", Call::query(r#"{"config":{}}"#)),
+            Case::new("exchange_rate", Call::query(r#"{"exchange_rate":{}}"#)),
+            Case::new(
+                "staker",
+                Call::query(format!(
+                    r#"{{"staker":{{"address":"{}"}}}}"#,
+                    addr(SENDER)
+                )),
+            ),
+            Case::new("delegations", Call::query(r#"{"delegations":{}}"#)),
+            Case::new(
+                "delegation",
+                Call::query(format!(
+                    r#"{{"delegation":{{"validator":"{}"}}}}"#,
+                    VALIDATORS[0]
+                )),
+            ),
+            Case::new("validators", Call::query(r#"{"validators":{}}"#)),
+            Case::new("pending_rewards", Call::query(r#"{"pending_rewards":{}}"#)),
+            Case::new(
+                "vote_tally",
+                Call::query(r#"{"vote_tally":{"proposal_id":1}}"#),
+            )
+            .with_setup(vec![stake, signal]),
+        ],
+    )
+    .with_balances(vec![
+        (addr(SENDER), coins(1_000, &lst_denom)),
+        (contract.clone(), coins(20, DENOM)),
+    ])
+    .with_delegations(delegations)
+}
+
+pub fn wasteful_gas() -> ContractCases {
+    let work = r#"{"cpu_iterations":10000,"storage_writes":10,"value_size":1024}"#;
+    let writes = r#"{"burn_storage_writes":{"count":10,"value_size":1024}}"#;
+    let fill = r#"{"fill_storage":{"keys":10,"value_size":1024}}"#;
+    // the same vectors as the contract's unit tests, so every check passes
+    let hash = "r5DXelxJV9X1UZxRqmacav2NWPf366bite/nEHshdWs=";
+    let secp_signature =
+        "CIbOaZxO56b5ztCrY0oVCf6UEJad5OKBnOFN8TVxTlxiQnHWWmryudyThzTYpA+uVVdXfIRr1W/8hXChsj2sMA==";
+    let secp_pubkey = "ApicC3bLVjlx/cm+8x7AbDVg8ySdbunl2DxXYlWW4F9v";
+    let ed_signature =
+        "WvZbdsq0/mCYJ9hAsrdL11jKxVF2umagh6rSrIJMjISnGC5Ud9H2d0wQDh/7kX0dd0l8Kx5tCFmWS1ZfQ8VdAg==";
+    let ed_pubkey = "/RckOFqgx1tk+3jNYC+h2ZH96/drE8WO1wLqyDXp9hg=";
+    let ed_message = "d2FzdGVmdWwtZ2Fz";
+    let contract = addr("cosmos2contract");
+    // `{"no_gas":{}}`, called on the contract itself
+    let gas_limited = Call::execute(format!(
+        r#"{{"call_with_gas_limit":{{"target":"{contract}","msg":"eyJub19nYXMiOnt9fQ==","gas_limit":100000}}}}"#
+    ));
+    let out_of_gas =
+        Call::reply(r#"{"id":2,"payload":"","gas_used":100000,"result":{"error":"out of gas"}}"#);
+    let waste_reply =
+        Call::reply(r#"{"id":1,"payload":"","gas_used":50000,"result":{"error":"wasted"}}"#);
+    ContractCases::new(
+        "wasteful_gas",
+        "{}",
+        vec![
+            Case::new("no_gas", Call::execute(r#"{"no_gas":{}}"#)),
+            Case::new("waste_gas", Call::execute(r#"{"waste_gas":{}}"#)),
+            Case::new(
+                "burn_cpu",
+                Call::execute(r#"{"burn_cpu":{"iterations":100000}}"#),
+            ),
+            Case::new("burn_storage_writes", Call::execute(writes)),
+            Case::new(
+                "burn_storage_reads",
+                Call::execute(r#"{"burn_storage_reads":{"count":10}}"#),
+            )
+            .with_setup(vec![Call::execute(writes)]),
+            Case::new(
+                "burn_memory",
+                Call::execute(r#"{"burn_memory":{"bytes":1048576}}"#),
+            ),
+            Case::new(
+                "waste_then_revert",
+                Call::execute(format!(r#"{{"waste_then_revert":{{"work":{work}}}}}"#)),
+            ),
+            // the submessage is returned, not run, so this only covers the dispatch
+            Case::new(
+                "waste_in_submsg",
+                Call::execute(format!(
+                    r#"{{"waste_in_submsg":{{"work":{work},"reply_on":"error"}}}}"#
+                )),
+            ),
+            Case::new("reply", waste_reply.clone()),
+            // the mock querier can't query contracts, so this records the error
+            Case::new(
+                "self_query",
+                Call::execute(r#"{"self_query":{"count":10,"iterations":1000}}"#),
+            ),
+            Case::new(
+                "emit_events",
+                Call::execute(
+                    r#"{"emit_events":{"events":10,"attributes_per_event":10,"value_len":64}}"#,
+                ),
+            ),
+            Case::new(
+                "large_data",
+                Call::execute(r#"{"large_data":{"bytes":65536}}"#),
+            ),
+            Case::new("call_with_gas_limit", gas_limited.clone()),
+            Case::new("record_gas_limited_failure", out_of_gas.clone())
+                .with_setup(vec![gas_limited.clone()]),
+            Case::new("fill_storage", Call::execute(fill)),
+            Case::new(
+                "clear_storage",
+                Call::execute(r#"{"clear_storage":{"limit":10}}"#),
+            )
+            .with_setup(vec![Call::execute(fill)]),
+            Case::new(
+                "secp256k1_verify",
+                Call::execute(format!(
+                    r#"{{"secp256k1_verify":{{"count":10,"message_hash":"{hash}","signature":"{secp_signature}","public_key":"{secp_pubkey}"}}}}"#
+                )),
+            ),
+            Case::new(
+                "secp256k1_recover_pubkey",
+                Call::execute(format!(
+                    r#"{{"secp256k1_recover_pubkey":{{"count":10,"message_hash":"{hash}","signature":"{secp_signature}","recovery_param":0}}}}"#
+                )),
+            ),
+            Case::new(
+                "ed25519_batch_verify",
+                Call::execute(format!(
+                    r#"{{"ed25519_batch_verify":{{"count":10,"messages":["{ed_message}","{ed_message}","{ed_message}","{ed_message}"],"signatures":["{ed_signature}","{ed_signature}","{ed_signature}","{ed_signature}"],"public_keys":["{ed_pubkey}"]}}}}"#
+                )),
+            ),
+            Case::new(
+                "sha256",
+                Call::execute(r#"{"sha256":{"iterations":1000,"input_size":1024}}"#),
+            ),
+            Case::new(
+                "expensive_query",
+                Call::query(r#"{"expensive_query":{"iterations":100000}}"#),
+            ),
+            Case::new(
+                "large_response",
+                Call::query(r#"{"large_response":{"bytes":65536}}"#),
+            ),
+            Case::new("storage_stats", Call::query(r#"{"storage_stats":{}}"#)),
+            Case::new("last_reply", Call::query(r#"{"last_reply":{}}"#))
+                .with_setup(vec![waste_reply]),
+            Case::new(
+                "gas_limited_call",
+                Call::query(r#"{"gas_limited_call":{}}"#),
+            )
+            .with_setup(vec![gas_limited, out_of_gas]),
+        ],
+    )
+}
+
+pub fn infinite_loop() -> ContractCases {
+    // runs until the gas limit is exhausted
+    ContractCases::new(
+        "infinite_loop",
+        "{}",
+        vec![Case::new("run", Call::execute(r#"{"run":{}}"#))],
+    )
+}
+
+/// A valid address for the mock API, which checks bech32 addresses.
+pub fn addr(name: &str) -> String {
+    cosmwasm_std::testing::MockApi::default()
+        .addr_make(name)
+        .to_string()
+}
@@ -0,0 +1,19 @@
+use std::path::PathBuf;
+
+use cosmwasm_vm::VmError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum BenchError {
+    #[error("{0}")]
+    Vm(#[from] VmError),
+
+    #[error("Cannot access {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("Invalid snapshot line {line}: {content}")]
+    InvalidSnapshot { line: usize, content: String },
+}
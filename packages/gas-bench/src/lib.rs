@@ -0,0 +1,9 @@
+pub mod cases;
+mod error;
+pub mod runner;
+pub mod snapshot;
+
+#[cfg(test)]
+pub mod testing;
+
+pub use crate::error::BenchError;
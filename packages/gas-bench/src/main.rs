@@ -0,0 +1,78 @@
+use std::process::ExitCode;
+
+use anyhow::{bail, Context};
+use cosmwasm_std::to_json_string;
+
+use gas_bench::cases;
+use gas_bench::runner::{Measurement, Runner};
+
+const USAGE: &str = "usage: gas-bench [--json] [--artifacts <dir>] [contract...]";
+const DEFAULT_ARTIFACTS: &str = "target/wasm32-unknown-unknown/release";
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> anyhow::Result<()> {
+    let mut json = false;
+    let mut artifacts = DEFAULT_ARTIFACTS.to_string();
+    let mut only = vec![];
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--artifacts" => artifacts = args.next().context(USAGE)?,
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return Ok(());
+            }
+            flag if flag.starts_with('-') => bail!("unknown flag {flag}\n{USAGE}"),
+            contract => only.push(contract.replace('-', "_")),
+        }
+    }
+
+    let runner = Runner::new(artifacts);
+    let mut measurements = vec![];
+    for contract in cases::all() {
+        if !only.is_empty() && !only.iter().any(|c| c == contract.contract) {
+            continue;
+        }
+        measurements.extend(runner.run(&contract).with_context(|| {
+            format!(
+                "benchmarking {}, build the contracts first as described in packages/gas-bench/README.md",
+                contract.contract
+            )
+        })?);
+    }
+
+    if json {
+        println!("{}", to_json_string(&measurements)?);
+    } else {
+        print_table(&measurements);
+    }
+    Ok(())
+}
+
+fn print_table(measurements: &[Measurement]) {
+    println!(
+        "{:<14} {:<22} {:<12} {:>16} {:>10}  error",
+        "contract", "case", "kind", "gas", "sdk gas"
+    );
+    for m in measurements {
+        println!(
+            "{:<14} {:<22} {:<12} {:>16} {:>10}  {}",
+            m.contract,
+            m.case,
+            m.kind,
+            m.gas_used,
+            m.sdk_gas,
+            m.error.as_deref().unwrap_or("")
+        );
+    }
+}
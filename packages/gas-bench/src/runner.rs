@@ -0,0 +1,262 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use cosmwasm_std::testing::message_info;
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, Binary, ContractResult, Decimal, Empty, Env, QueryRequest,
+    Reply, SystemResult, Validator,
+};
+use cosmwasm_vm::internals::{check_wasm, Logger};
+use cosmwasm_vm::testing::{mock_env, MockApi, MockQuerier, MockStorage};
+use cosmwasm_vm::{
+    call_execute, call_instantiate, call_query, call_reply, capabilities_from_csv, Backend,
+    BackendError, BackendResult, GasInfo, Instance, InstanceOptions, Querier, Size, VmResult,
+    WasmLimits,
+};
+use serde::Serialize;
+
+use crate::cases::{addr, Call, ContractCases, CREATOR, DENOM, VALIDATORS};
+use crate::BenchError;
+
+/// wasmd's ratio between CosmWasm gas and SDK gas.
+pub const GAS_MULTIPLIER: u64 = 140_000;
+/// Gas available to every case, the CosmWasm equivalent of 10M SDK gas.
+pub const GAS_LIMIT: u64 = 10_000_000 * GAS_MULTIPLIER;
+/// The capabilities of a CosmWasm 2.1 chain such as Nibiru.
+const CAPABILITIES: &str = "iterator,staking,stargate,cosmwasm_1_1,cosmwasm_1_2,cosmwasm_1_3,cosmwasm_1_4,cosmwasm_2_0,cosmwasm_2_1";
+/// wasmd's default memory limit per instance.
+const MEMORY_LIMIT: Size = Size::mebi(32);
+/// What `MockQuerier` charges per query and per byte of the response.
+const GAS_COST_QUERY_FLAT: u64 = 100_000;
+const GAS_COST_QUERY_RESPONSE_MULTIPLIER: u64 = 100;
+
+type MockInstance = Instance<MockApi, MockStorage, ChainQuerier>;
+
+/// `MockQuerier` along with canned answers to the gRPC queries of Nibiru
+/// modules, charged the same way.
+pub struct ChainQuerier {
+    base: MockQuerier,
+    grpc: Vec<(String, Binary)>,
+}
+
+impl ChainQuerier {
+    fn grpc_response(&self, request: &[u8]) -> Option<&Binary> {
+        let Ok(QueryRequest::Grpc(query)) = from_json::<QueryRequest<Empty>>(request) else {
+            return None;
+        };
+        self.grpc
+            .iter()
+            .find(|(path, _)| *path == query.path)
+            .map(|(_, response)| response)
+    }
+}
+
+impl Querier for ChainQuerier {
+    fn query_raw(
+        &self,
+        request: &[u8],
+        gas_limit: u64,
+    ) -> BackendResult<SystemResult<ContractResult<Binary>>> {
+        let Some(response) = self.grpc_response(request) else {
+            return self.base.query_raw(request, gas_limit);
+        };
+        let response = SystemResult::Ok(ContractResult::Ok(response.clone()));
+        let response_len = to_json_binary(&response).map_or(0, |r| r.len() as u64);
+        let gas_info = GasInfo::with_externally_used(
+            GAS_COST_QUERY_FLAT + GAS_COST_QUERY_RESPONSE_MULTIPLIER * response_len,
+        );
+        if gas_info.externally_used > gas_limit {
+            return (Err(BackendError::out_of_gas()), gas_info);
+        }
+        (Ok(response), gas_info)
+    }
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Measurement {
+    pub contract: String,
+    pub case: String,
+    /// `instantiate`, `execute` or `query`.
+    pub kind: String,
+    /// CosmWasm gas, including the gas charged for host calls.
+    pub gas_used: u64,
+    /// `gas_used` in SDK gas, rounded up.
+    pub sdk_gas: u64,
+    /// Set when the call failed, failed calls still use gas.
+    pub error: Option<String>,
+}
+
+/// Runs the compiled contracts found in `artifacts`, as built by
+/// `cargo wasm` or the workspace optimizer.
+pub struct Runner {
+    artifacts: PathBuf,
+}
+
+impl Runner {
+    pub fn new(artifacts: impl Into<PathBuf>) -> Self {
+        Runner {
+            artifacts: artifacts.into(),
+        }
+    }
+
+    pub fn wasm_path(&self, contract: &str) -> PathBuf {
+        self.artifacts.join(format!("{contract}.wasm"))
+    }
+
+    /// Measures the instantiation of `contract` and then each of its cases,
+    /// every case on a fresh instance so that they don't affect each other.
+    pub fn run(&self, contract: &ContractCases) -> Result<Vec<Measurement>, BenchError> {
+        let wasm = read_wasm(&self.wasm_path(contract.contract))?;
+        check_wasm(
+            &wasm,
+            &capabilities_from_csv(CAPABILITIES),
+            &WasmLimits::default(),
+            Logger::Off,
+        )?;
+        let env = mock_env();
+        let creator = Addr::unchecked(addr(CREATOR));
+
+        let mut instance = new_instance(&wasm, contract)?;
+        let (gas_used, error) = metered(&mut instance, |instance| {
+            instantiate(instance, &env, &creator, &contract.instantiate)
+        });
+        let mut measurements = vec![measurement(
+            contract.contract,
+            "instantiate",
+            "instantiate",
+            gas_used,
+            error,
+        )];
+
+        for case in &contract.cases {
+            let mut instance = new_instance(&wasm, contract)?;
+            let setup =
+                instantiate(&mut instance, &env, &creator, &contract.instantiate).and_then(|_| {
+                    case.setup
+                        .iter()
+                        .try_for_each(|call| dispatch(&mut instance, &env, call))
+                });
+            let (gas_used, error) = match setup {
+                Ok(()) => metered(&mut instance, |instance| {
+                    dispatch(instance, &env, &case.call)
+                }),
+                Err(err) => (0, Some(format!("setup failed: {err}"))),
+            };
+            measurements.push(measurement(
+                contract.contract,
+                case.name,
+                case.call.kind(),
+                gas_used,
+                error,
+            ));
+        }
+        Ok(measurements)
+    }
+}
+
+fn read_wasm(path: &Path) -> Result<Vec<u8>, BenchError> {
+    fs::read(path).map_err(|source| BenchError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// An instance backed by a mock chain with the balances and delegations
+/// `contract` expects.
+fn new_instance(wasm: &[u8], contract: &ContractCases) -> VmResult<MockInstance> {
+    let balances: Vec<(&str, &[_])> = contract
+        .balances
+        .iter()
+        .map(|(address, coins)| (address.as_str(), coins.as_slice()))
+        .collect();
+    let mut querier = MockQuerier::new(&balances);
+    let validators: Vec<Validator> = VALIDATORS
+        .iter()
+        .map(|address| {
+            Validator::create(
+                address.to_string(),
+                Decimal::percent(5),
+                Decimal::percent(20),
+                Decimal::percent(1),
+            )
+        })
+        .collect();
+    querier.update_staking(DENOM, &validators, &contract.delegations);
+
+    let backend = Backend {
+        api: MockApi::default(),
+        storage: MockStorage::default(),
+        querier: ChainQuerier {
+            base: querier,
+            grpc: contract.grpc.clone(),
+        },
+    };
+    let options = InstanceOptions {
+        gas_limit: GAS_LIMIT,
+    };
+    Instance::from_code(wasm, backend, options, Some(MEMORY_LIMIT))
+}
+
+/// Runs `f` and returns the gas it used along with its error, if any.
+fn metered(
+    instance: &mut MockInstance,
+    f: impl FnOnce(&mut MockInstance) -> Result<(), String>,
+) -> (u64, Option<String>) {
+    let before = instance.get_gas_left();
+    let result = f(instance);
+    (before - instance.get_gas_left(), result.err())
+}
+
+/// Contract errors and VM errors, such as running out of gas, are both
+/// reported as strings.
+fn instantiate(
+    instance: &mut MockInstance,
+    env: &Env,
+    sender: &Addr,
+    msg: &Binary,
+) -> Result<(), String> {
+    call_instantiate::<_, _, _, Empty>(instance, env, &message_info(sender, &[]), msg)
+        .map_err(|err| err.to_string())?
+        .into_result()
+        .map(|_| ())
+}
+
+fn dispatch(instance: &mut MockInstance, env: &Env, call: &Call) -> Result<(), String> {
+    match call {
+        Call::Execute { sender, msg, funds } => {
+            let info = message_info(&Addr::unchecked(sender), funds);
+            call_execute::<_, _, _, Empty>(instance, env, &info, msg)
+                .map_err(|err| err.to_string())?
+                .into_result()
+                .map(|_| ())
+        }
+        Call::Query { msg } => call_query(instance, env, msg)
+            .map_err(|err| err.to_string())?
+            .into_result()
+            .map(|_| ()),
+        Call::Reply { msg } => {
+            let reply: Reply = from_json(msg).map_err(|err| err.to_string())?;
+            call_reply::<_, _, _, Empty>(instance, env, &reply)
+                .map_err(|err| err.to_string())?
+                .into_result()
+                .map(|_| ())
+        }
+    }
+}
+
+fn measurement(
+    contract: &str,
+    case: &str,
+    kind: &str,
+    gas_used: u64,
+    error: Option<String>,
+) -> Measurement {
+    Measurement {
+        contract: contract.to_string(),
+        case: case.to_string(),
+        kind: kind.to_string(),
+        gas_used,
+        sdk_gas: gas_used.div_ceil(GAS_MULTIPLIER),
+        error,
+    }
+}
@@ -0,0 +1,153 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use crate::runner::Measurement;
+use crate::BenchError;
+
+/// Gas used by each case of a contract and whether the call succeeded,
+/// checked in under `snapshots/` as one `<case> <gas_used> <ok|error>` line
+/// per case.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    pub cases: BTreeMap<String, Outcome>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Outcome {
+    pub gas_used: u64,
+    /// A case that starts or stops failing changes behavior, not just cost.
+    pub failed: bool,
+}
+
+impl Outcome {
+    fn label(&self) -> &'static str {
+        if self.failed {
+            "error"
+        } else {
+            "ok"
+        }
+    }
+}
+
+pub fn path(contract: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("snapshots")
+        .join(format!("{contract}.snap"))
+}
+
+impl Snapshot {
+    pub fn from_measurements(measurements: &[Measurement]) -> Self {
+        Snapshot {
+            cases: measurements
+                .iter()
+                .map(|m| {
+                    let outcome = Outcome {
+                        gas_used: m.gas_used,
+                        failed: m.error.is_some(),
+                    };
+                    (m.case.clone(), outcome)
+                })
+                .collect(),
+        }
+    }
+
+    pub fn parse(content: &str) -> Result<Self, BenchError> {
+        let mut cases = BTreeMap::new();
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = || BenchError::InvalidSnapshot {
+                line: i + 1,
+                content: line.to_string(),
+            };
+            let [case, used, outcome] = line
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .try_into()
+                .map_err(|_| invalid())?;
+            let failed = match outcome {
+                "ok" => false,
+                "error" => true,
+                _ => return Err(invalid()),
+            };
+            let outcome = Outcome {
+                gas_used: used.parse().map_err(|_| invalid())?,
+                failed,
+            };
+            cases.insert(case.to_string(), outcome);
+        }
+        Ok(Snapshot { cases })
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::from("# case gas_used outcome\n");
+        for (case, outcome) in &self.cases {
+            out.push_str(&format!(
+                "{case} {} {}\n",
+                outcome.gas_used,
+                outcome.label()
+            ));
+        }
+        out
+    }
+
+    /// `None` if no snapshot has been recorded at `path` yet.
+    pub fn load(path: &Path) -> Result<Option<Self>, BenchError> {
+        match fs::read_to_string(path) {
+            Ok(content) => Self::parse(&content).map(Some),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(source) => Err(BenchError::Io {
+                path: path.to_path_buf(),
+                source,
+            }),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), BenchError> {
+        let write = || {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(path, self.render())
+        };
+        write().map_err(|source| BenchError::Io {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// Describes every case whose gas moved by more than `tolerance_percent`
+    /// from this snapshot or that started or stopped failing, as well as cases
+    /// added or removed since.
+    pub fn diff(&self, actual: &Snapshot, tolerance_percent: u64) -> Vec<String> {
+        let mut diffs = vec![];
+        for (case, expected) in &self.cases {
+            let Some(outcome) = actual.cases.get(case) else {
+                diffs.push(format!("{case}: removed, was {}", expected.gas_used));
+                continue;
+            };
+            if outcome.failed != expected.failed {
+                diffs.push(format!(
+                    "{case}: {} -> {}",
+                    expected.label(),
+                    outcome.label()
+                ));
+            }
+            let (expected, used) = (expected.gas_used, outcome.gas_used);
+            let allowed = u128::from(expected) * u128::from(tolerance_percent) / 100;
+            if u128::from(used.abs_diff(expected)) > allowed {
+                diffs.push(format!("{case}: {expected} -> {used}"));
+            }
+        }
+        for (case, outcome) in &actual.cases {
+            if !self.cases.contains_key(case) {
+                diffs.push(format!("{case}: added, uses {}", outcome.gas_used));
+            }
+        }
+        diffs
+    }
+}
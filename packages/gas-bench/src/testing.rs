@@ -0,0 +1,123 @@
+use std::path::PathBuf;
+
+use crate::cases::{self, ContractCases};
+use crate::runner::Runner;
+use crate::snapshot::{self, Outcome, Snapshot};
+use crate::BenchError;
+
+/// How far gas may move from the snapshot before a test fails.
+const TOLERANCE_PERCENT: u64 = 2;
+
+fn snapshot(entries: &[(&str, u64, bool)]) -> Snapshot {
+    Snapshot {
+        cases: entries
+            .iter()
+            .map(|(case, gas_used, failed)| {
+                let outcome = Outcome {
+                    gas_used: *gas_used,
+                    failed: *failed,
+                };
+                (case.to_string(), outcome)
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn test_snapshot_roundtrip() {
+    let expected = snapshot(&[
+        ("instantiate", 120_000, false),
+        ("get_counter", 45_000, true),
+    ]);
+    let rendered = expected.render();
+    assert_eq!(
+        rendered,
+        "# case gas_used outcome\nget_counter 45000 error\ninstantiate 120000 ok\n"
+    );
+    assert_eq!(Snapshot::parse(&rendered).unwrap(), expected);
+
+    for invalid in [
+        "get_counter lots ok",
+        "get_counter 45000",
+        "get_counter 45000 maybe",
+    ] {
+        let err = Snapshot::parse(&format!("# case gas_used outcome\n{invalid}\n")).unwrap_err();
+        assert!(matches!(err, BenchError::InvalidSnapshot { line: 2, .. }));
+    }
+}
+
+#[test]
+fn test_snapshot_diff() {
+    let expected = snapshot(&[("a", 1_000, false), ("b", 1_000, false), ("c", 1_000, true)]);
+
+    // moves within the tolerance are accepted either way
+    let actual = snapshot(&[("a", 1_020, false), ("b", 980, false), ("c", 1_000, true)]);
+    assert!(expected.diff(&actual, 2).is_empty());
+
+    let actual = snapshot(&[("a", 1_021, false), ("b", 1_000, true), ("d", 5, false)]);
+    assert_eq!(
+        expected.diff(&actual, 2),
+        vec![
+            "a: 1000 -> 1021".to_string(),
+            "b: ok -> error".to_string(),
+            "c: removed, was 1000".to_string(),
+            "d: added, uses 5".to_string(),
+        ]
+    );
+}
+
+fn artifacts() -> PathBuf {
+    match std::env::var_os("GAS_BENCH_ARTIFACTS") {
+        Some(dir) => dir.into(),
+        None => PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../target/wasm32-unknown-unknown/release"),
+    }
+}
+
+/// Compares the gas used by `contract` with its checked-in snapshot, or
+/// records it when `UPDATE_GAS_SNAPSHOTS` is set. A missing snapshot fails
+/// rather than passing silently.
+fn check_snapshot(contract: ContractCases) {
+    let measurements = Runner::new(artifacts())
+        .run(&contract)
+        .unwrap_or_else(|err| panic!("{err}, build the contracts first, see the README"));
+    let actual = Snapshot::from_measurements(&measurements);
+
+    let path = snapshot::path(contract.contract);
+    if std::env::var_os("UPDATE_GAS_SNAPSHOTS").is_some() {
+        actual.save(&path).unwrap();
+        return;
+    }
+    let expected = Snapshot::load(&path).unwrap().unwrap_or_else(|| {
+        panic!(
+            "no gas snapshot at {}, record it with UPDATE_GAS_SNAPSHOTS=1",
+            path.display()
+        )
+    });
+    let diffs = expected.diff(&actual, TOLERANCE_PERCENT);
+    assert!(
+        diffs.is_empty(),
+        "gas of {} moved by more than {TOLERANCE_PERCENT}%:\n{}\n\
+         rerun with UPDATE_GAS_SNAPSHOTS=1 if this is expected",
+        contract.contract,
+        diffs.join("\n")
+    );
+}
+
+#[test]
+#[ignore = "needs the compiled contracts, see the README"]
+fn test_counter_gas() {
+    check_snapshot(cases::counter());
+}
+
+#[test]
+#[ignore = "needs the compiled contracts, see the README"]
+fn test_bank_transfer_gas() {
+    check_snapshot(cases::bank_transfer());
+}
+
+#[test]
+#[ignore = "needs the compiled contracts, see the README"]
+fn test_oracle_query_gas() {
+    check_snapshot(cases::oracle_query());
+}